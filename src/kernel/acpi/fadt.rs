@@ -0,0 +1,98 @@
+use super::generic_address::GenericAddress;
+use super::sdt::{read_u16, read_u32, read_u64, read_u8, Sdt};
+
+/// `flags`: the reset register is supported.
+const FLAG_RESET_REGISTER: u32 = 1 << 10;
+/// `boot_architecture_flags`: the machine has an 8042 keyboard controller.
+const BOOT_ARCHITECTURE_8042: u16 = 1 << 1;
+
+/// The Fixed ACPI Description Table. Fields which are not part of an older,
+/// shorter table are zero.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct Fadt {
+    pub revision: u8,
+    pub dsdt_address: u64,
+    pub sci_interrupt: u16,
+    pub smi_command_port: u32,
+    pub acpi_enable: u8,
+    pub pm1a_control_block: u32,
+    pub pm1b_control_block: u32,
+    pub pm_timer_block: u32,
+    /// CMOS RTC register of the century, 0 if not supported.
+    pub century_register: u8,
+    pub boot_architecture_flags: u16,
+    pub flags: u32,
+    pub reset_register: Option<GenericAddress>,
+    pub reset_value: u8,
+}
+
+impl Fadt {
+    pub fn parse(sdt: &Sdt) -> Fadt {
+        let bytes = sdt.bytes();
+        let has_field = |offset: usize, size: usize| offset + size <= bytes.len();
+        let u8_at = |offset| {
+            if has_field(offset, 1) {
+                read_u8(bytes, offset)
+            } else {
+                0
+            }
+        };
+        let u16_at = |offset| {
+            if has_field(offset, 2) {
+                read_u16(bytes, offset)
+            } else {
+                0
+            }
+        };
+        let u32_at = |offset| {
+            if has_field(offset, 4) {
+                read_u32(bytes, offset)
+            } else {
+                0
+            }
+        };
+        let u64_at = |offset| {
+            if has_field(offset, 8) {
+                read_u64(bytes, offset)
+            } else {
+                0
+            }
+        };
+
+        let flags = u32_at(112);
+        let x_dsdt = u64_at(140);
+
+        Fadt {
+            revision: sdt.revision(),
+            dsdt_address: if x_dsdt != 0 {
+                x_dsdt
+            } else {
+                u32_at(40) as u64
+            },
+            sci_interrupt: u16_at(46),
+            smi_command_port: u32_at(48),
+            acpi_enable: u8_at(52),
+            pm1a_control_block: u32_at(64),
+            pm1b_control_block: u32_at(68),
+            pm_timer_block: u32_at(76),
+            century_register: u8_at(108),
+            boot_architecture_flags: u16_at(109),
+            flags,
+            reset_register: if has_field(116, GenericAddress::SIZE)
+                && flags & FLAG_RESET_REGISTER != 0
+            {
+                Some(GenericAddress::parse(bytes, 116))
+            } else {
+                None
+            },
+            reset_value: u8_at(128),
+        }
+    }
+
+    /// The boot architecture flags are reserved before revision 2, so an
+    /// 8042 is assumed to be present there.
+    pub fn has_8042(&self) -> bool {
+        self.revision < 2 || self.boot_architecture_flags & BOOT_ARCHITECTURE_8042 != 0
+    }
+}
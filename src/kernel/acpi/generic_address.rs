@@ -0,0 +1,39 @@
+use super::sdt::{read_u64, read_u8};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressSpace {
+    SystemMemory,
+    SystemIo,
+    PciConfiguration,
+    Other(u8),
+}
+
+/// Location of a register in one of the address spaces (ACPI Generic Address Structure).
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct GenericAddress {
+    pub address_space: AddressSpace,
+    pub bit_width: u8,
+    pub bit_offset: u8,
+    pub access_size: u8,
+    pub address: u64,
+}
+
+impl GenericAddress {
+    pub const SIZE: usize = 12;
+
+    pub fn parse(bytes: &[u8], offset: usize) -> GenericAddress {
+        GenericAddress {
+            address_space: match read_u8(bytes, offset) {
+                0 => AddressSpace::SystemMemory,
+                1 => AddressSpace::SystemIo,
+                2 => AddressSpace::PciConfiguration,
+                other => AddressSpace::Other(other),
+            },
+            bit_width: read_u8(bytes, offset + 1),
+            bit_offset: read_u8(bytes, offset + 2),
+            access_size: read_u8(bytes, offset + 3),
+            address: read_u64(bytes, offset + 4),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use super::generic_address::GenericAddress;
+use super::sdt::{read_u16, read_u32, read_u8, Sdt};
+use super::AcpiError;
+use crate::memory::phys_addr::PhysAddr;
+
+const LENGTH: usize = 56;
+
+/// The HPET Description Table.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct HpetTable {
+    pub event_timer_block_id: u32,
+    pub base_address: GenericAddress,
+    pub hpet_number: u8,
+    /// Minimum clock ticks for periodic mode without lost interrupts.
+    pub minimum_tick: u16,
+}
+
+impl HpetTable {
+    pub fn parse(sdt: &Sdt) -> Result<HpetTable, AcpiError> {
+        let bytes = sdt.bytes();
+        if bytes.len() < LENGTH {
+            return Err(AcpiError::InvalidLength(sdt.signature()));
+        }
+        let base_address = GenericAddress::parse(bytes, 40);
+        PhysAddr::try_new(base_address.address)?;
+
+        Ok(HpetTable {
+            event_timer_block_id: read_u32(bytes, 36),
+            base_address,
+            hpet_number: read_u8(bytes, 52),
+            minimum_tick: read_u16(bytes, 53),
+        })
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::vec::Vec;
+
+use super::sdt::{read_u16, read_u32, read_u64, read_u8, Sdt};
+use super::AcpiError;
+use crate::apic::{ApicTopology, InterruptOverride, IoApicInfo};
+use crate::memory::phys_addr::PhysAddr;
+
+/// Size of the header and the fixed fields before the entries.
+const ENTRIES_OFFSET: usize = 44;
+
+const FLAG_PCAT_COMPAT: u32 = 1 << 0;
+
+const ENTRY_LOCAL_APIC: u8 = 0;
+const ENTRY_IO_APIC: u8 = 1;
+const ENTRY_INTERRUPT_OVERRIDE: u8 = 2;
+const ENTRY_LOCAL_APIC_ADDRESS_OVERRIDE: u8 = 5;
+const ENTRY_LOCAL_X2APIC: u8 = 9;
+
+const PROCESSOR_ENABLED: u32 = 1 << 0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Processor {
+    pub processor_uid: u32,
+    pub apic_id: u32,
+    /// Disabled processors can not be started.
+    pub enabled: bool,
+}
+
+/// The Multiple APIC Description Table.
+#[derive(Debug, Clone)]
+pub struct Madt {
+    pub local_apic_address: PhysAddr,
+    /// The system also has the 8259 PICs.
+    #[allow(dead_code)]
+    pub has_legacy_pics: bool,
+    pub processors: Vec<Processor>,
+    pub io_apics: Vec<IoApicInfo>,
+    pub overrides: Vec<InterruptOverride>,
+}
+
+impl Madt {
+    pub fn parse(sdt: &Sdt) -> Result<Madt, AcpiError> {
+        let bytes = sdt.bytes();
+        if bytes.len() < ENTRIES_OFFSET {
+            return Err(AcpiError::InvalidLength(sdt.signature()));
+        }
+
+        let mut madt = Madt {
+            local_apic_address: PhysAddr::new(read_u32(bytes, 36) as u64),
+            has_legacy_pics: read_u32(bytes, 40) & FLAG_PCAT_COMPAT != 0,
+            processors: Vec::new(),
+            io_apics: Vec::new(),
+            overrides: Vec::new(),
+        };
+
+        let mut offset = ENTRIES_OFFSET;
+        while offset + 2 <= bytes.len() {
+            let typ = read_u8(bytes, offset);
+            let length = read_u8(bytes, offset + 1) as usize;
+            if length < 2 || offset + length > bytes.len() {
+                break;
+            }
+            let entry = &bytes[offset..offset + length];
+
+            match typ {
+                ENTRY_LOCAL_APIC if length >= 8 => madt.processors.push(Processor {
+                    processor_uid: read_u8(entry, 2) as u32,
+                    apic_id: read_u8(entry, 3) as u32,
+                    enabled: read_u32(entry, 4) & PROCESSOR_ENABLED != 0,
+                }),
+                ENTRY_IO_APIC if length >= 12 => madt.io_apics.push(IoApicInfo {
+                    id: read_u8(entry, 2),
+                    address: PhysAddr::new(read_u32(entry, 4) as u64),
+                    gsi_base: read_u32(entry, 8),
+                }),
+                ENTRY_INTERRUPT_OVERRIDE if length >= 10 => madt.overrides.push(parse_override(
+                    read_u8(entry, 3),
+                    read_u32(entry, 4),
+                    read_u16(entry, 8),
+                )),
+                ENTRY_LOCAL_APIC_ADDRESS_OVERRIDE if length >= 12 => {
+                    madt.local_apic_address = PhysAddr::try_new(read_u64(entry, 4))?
+                }
+                ENTRY_LOCAL_X2APIC if length >= 16 => madt.processors.push(Processor {
+                    processor_uid: read_u32(entry, 12),
+                    apic_id: read_u32(entry, 4),
+                    enabled: read_u32(entry, 8) & PROCESSOR_ENABLED != 0,
+                }),
+                _ => {}
+            }
+
+            offset += length;
+        }
+
+        Ok(madt)
+    }
+
+    pub fn apic_topology(&self) -> ApicTopology {
+        ApicTopology {
+            local_apic_address: self.local_apic_address,
+            io_apics: self.io_apics.clone(),
+            overrides: self.overrides.clone(),
+        }
+    }
+}
+
+/// Polarity and trigger mode of 0 mean "conforms to the bus", which is
+/// active high and edge triggered for ISA.
+fn parse_override(irq: u8, gsi: u32, flags: u16) -> InterruptOverride {
+    InterruptOverride {
+        irq,
+        gsi,
+        active_low: flags & 0b11 == 0b11,
+        level_triggered: (flags >> 2) & 0b11 == 0b11,
+    }
+}
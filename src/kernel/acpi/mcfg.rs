@@ -0,0 +1,38 @@
+use alloc::vec::Vec;
+
+use super::sdt::{read_u16, read_u64, read_u8, Sdt};
+
+const ENTRIES_OFFSET: usize = 44;
+const ENTRY_SIZE: usize = 16;
+
+/// Memory mapped PCIe configuration space of a range of buses.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct McfgEntry {
+    pub base_address: u64,
+    pub segment_group: u16,
+    pub start_bus: u8,
+    pub end_bus: u8,
+}
+
+/// The PCI Express memory mapped configuration table.
+#[derive(Debug, Clone)]
+pub struct Mcfg {
+    pub entries: Vec<McfgEntry>,
+}
+
+impl Mcfg {
+    pub fn parse(sdt: &Sdt) -> Mcfg {
+        let bytes = sdt.bytes();
+        let entries = bytes[ENTRIES_OFFSET.min(bytes.len())..]
+            .chunks_exact(ENTRY_SIZE)
+            .map(|entry| McfgEntry {
+                base_address: read_u64(entry, 0),
+                segment_group: read_u16(entry, 8),
+                start_bus: read_u8(entry, 10),
+                end_bus: read_u8(entry, 11),
+            })
+            .collect();
+        Mcfg { entries }
+    }
+}
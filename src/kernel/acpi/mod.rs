@@ -0,0 +1,188 @@
+pub mod fadt;
+pub mod generic_address;
+pub mod hpet;
+pub mod madt;
+pub mod mcfg;
+pub mod rsdp;
+pub mod sdt;
+
+use alloc::vec::Vec;
+
+use self::fadt::Fadt;
+use self::hpet::HpetTable;
+use self::madt::Madt;
+use self::mcfg::Mcfg;
+use self::rsdp::{Rsdp, RsdpSource};
+use self::sdt::{read_u32, read_u64, Sdt, Signature, HEADER_SIZE};
+use crate::memory::paging::mapper::MapToError;
+use crate::memory::phys_addr::PhysAddrNotValid;
+use crate::multiboot::BootInformation;
+use crate::ylib::sync::mutex::Mutex;
+
+#[derive(Debug)]
+pub enum AcpiError {
+    RsdpNotFound,
+    InvalidLength(Signature),
+    InvalidChecksum(Signature),
+    /// The RSDP points to a table which is not a RSDT or XSDT.
+    InvalidRootTable(Signature),
+    /// A table contains a physical address with bits above bit 51 set.
+    InvalidAddress,
+    Mapping(MapToError),
+}
+
+impl From<PhysAddrNotValid> for AcpiError {
+    fn from(_: PhysAddrNotValid) -> Self {
+        AcpiError::InvalidAddress
+    }
+}
+
+impl From<MapToError> for AcpiError {
+    fn from(error: MapToError) -> Self {
+        AcpiError::Mapping(error)
+    }
+}
+
+/// The ACPI tables the kernel knows about.
+#[derive(Debug, Clone)]
+pub struct AcpiTables {
+    pub rsdp: Rsdp,
+    /// All tables referenced by the RSDT or XSDT.
+    pub tables: Vec<Sdt>,
+    pub madt: Option<Madt>,
+    pub fadt: Option<Fadt>,
+    pub hpet: Option<HpetTable>,
+    pub mcfg: Option<Mcfg>,
+}
+
+static ACPI_TABLES: Mutex<Option<AcpiTables>> = Mutex::new(None);
+
+/// Finds and parses the ACPI tables. Tables with an invalid checksum are skipped.
+pub fn init(boot_information: &BootInformation) -> Result<(), AcpiError> {
+    let rsdp = Rsdp::find(boot_information).ok_or(AcpiError::RsdpNotFound)?;
+    let root_table = unsafe { Sdt::load(rsdp.xsdt_address.unwrap_or(rsdp.rsdt_address as u64))? };
+
+    let entry_size = match root_table.signature() {
+        Signature::XSDT => 8,
+        Signature::RSDT => 4,
+        signature => return Err(AcpiError::InvalidRootTable(signature)),
+    };
+
+    let mut acpi_tables = AcpiTables {
+        rsdp,
+        tables: Vec::new(),
+        madt: None,
+        fadt: None,
+        hpet: None,
+        mcfg: None,
+    };
+
+    let entries = &root_table.bytes()[HEADER_SIZE..];
+    for entry in entries.chunks_exact(entry_size) {
+        let address = match entry_size {
+            8 => read_u64(entry, 0),
+            _ => read_u32(entry, 0) as u64,
+        };
+
+        let sdt = match unsafe { Sdt::load(address) } {
+            Ok(sdt) => sdt,
+            Err(error) => {
+                serial_println!("Skipping ACPI table at {:#x}: {:?}", address, error);
+                continue;
+            }
+        };
+
+        let parsed = match sdt.signature() {
+            Signature::MADT => Madt::parse(&sdt).map(|madt| acpi_tables.madt = Some(madt)),
+            Signature::FADT => {
+                acpi_tables.fadt = Some(Fadt::parse(&sdt));
+                Ok(())
+            }
+            Signature::HPET => HpetTable::parse(&sdt).map(|hpet| acpi_tables.hpet = Some(hpet)),
+            Signature::MCFG => {
+                acpi_tables.mcfg = Some(Mcfg::parse(&sdt));
+                Ok(())
+            }
+            _ => Ok(()),
+        };
+        if let Err(error) = parsed {
+            serial_println!("Skipping ACPI table at {:#x}: {:?}", address, error);
+            continue;
+        }
+        acpi_tables.tables.push(sdt);
+    }
+
+    report(&acpi_tables, &root_table);
+
+    *ACPI_TABLES.lock() = Some(acpi_tables);
+    Ok(())
+}
+
+/// Returns a copy of the parsed tables, if ACPI is available.
+pub fn tables() -> Option<AcpiTables> {
+    ACPI_TABLES.lock().clone()
+}
+
+fn report(acpi_tables: &AcpiTables, root_table: &Sdt) {
+    let rsdp = &acpi_tables.rsdp;
+    ok!(
+        "ACPI revision {} RSDP at {:#x} ({}) with {} at {:#x}",
+        rsdp.revision,
+        rsdp.address,
+        match rsdp.source {
+            RsdpSource::Multiboot => "multiboot",
+            RsdpSource::BiosArea => "BIOS area",
+        },
+        root_table.signature(),
+        root_table.address()
+    );
+
+    if let Some(madt) = &acpi_tables.madt {
+        for processor in madt.processors.iter() {
+            ok!(
+                "CPU {} with APIC id {} ({})",
+                processor.processor_uid,
+                processor.apic_id,
+                if processor.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+        for io_apic in madt.io_apics.iter() {
+            ok!(
+                "I/O APIC {} at {:?} with GSI base {}",
+                io_apic.id,
+                io_apic.address,
+                io_apic.gsi_base
+            );
+        }
+        for routing in madt.overrides.iter() {
+            ok!(
+                "IRQ {} is routed to GSI {} (active {}, {} triggered)",
+                routing.irq,
+                routing.gsi,
+                if routing.active_low { "low" } else { "high" },
+                if routing.level_triggered {
+                    "level"
+                } else {
+                    "edge"
+                }
+            );
+        }
+    }
+    if let Some(hpet) = &acpi_tables.hpet {
+        ok!("HPET at {:#x}", hpet.base_address.address);
+    }
+    if let Some(mcfg) = &acpi_tables.mcfg {
+        for entry in mcfg.entries.iter() {
+            ok!(
+                "PCIe configuration space of buses {} to {} at {:#x}",
+                entry.start_bus,
+                entry.end_bus,
+                entry.base_address
+            );
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::sdt::{checksum_valid, read_u32, read_u64};
+use crate::multiboot::BootInformation;
+
+const SIGNATURE: &[u8; 8] = b"RSD PTR ";
+
+/// Size of the RSDP of ACPI 1.0, which is covered by the first checksum.
+const RSDP_V1_SIZE: usize = 20;
+const RSDP_V2_SIZE: usize = 36;
+
+/// Real mode segment of the extended BIOS data area, stored in the BIOS data area.
+const EBDA_SEGMENT_POINTER: usize = 0x40e;
+const EBDA_SEARCH_SIZE: usize = 1024;
+const BIOS_AREA_START: usize = 0xe_0000;
+const BIOS_AREA_END: usize = 0x10_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsdpSource {
+    Multiboot,
+    BiosArea,
+}
+
+/// The root system description pointer.
+#[derive(Debug, Clone, Copy)]
+pub struct Rsdp {
+    pub address: usize,
+    pub source: RsdpSource,
+    pub revision: u8,
+    pub rsdt_address: u32,
+    /// Only available since ACPI 2.0.
+    pub xsdt_address: Option<u64>,
+}
+
+impl Rsdp {
+    /// Uses the copy of the bootloader and searches the BIOS areas otherwise.
+    pub fn find(boot_information: &BootInformation) -> Option<Rsdp> {
+        if let Some(tag) = boot_information.rsdp_tag() {
+            if let Some(rsdp) = unsafe { Rsdp::parse(tag.rsdp_address(), RsdpSource::Multiboot) } {
+                return Some(rsdp);
+            }
+        }
+
+        // The first GiB is identity mapped, so the BIOS areas can be read directly
+        let ebda_start = unsafe { *(EBDA_SEGMENT_POINTER as *const u16) as usize } << 4;
+        if ebda_start != 0 {
+            if let Some(rsdp) = Rsdp::scan(ebda_start, ebda_start + EBDA_SEARCH_SIZE) {
+                return Some(rsdp);
+            }
+        }
+        Rsdp::scan(BIOS_AREA_START, BIOS_AREA_END)
+    }
+
+    /// The RSDP is always aligned to 16 bytes.
+    fn scan(start: usize, end: usize) -> Option<Rsdp> {
+        (start..end)
+            .step_by(16)
+            .find_map(|address| unsafe { Rsdp::parse(address, RsdpSource::BiosArea) })
+    }
+
+    unsafe fn parse(address: usize, source: RsdpSource) -> Option<Rsdp> {
+        let bytes = core::slice::from_raw_parts(address as *const u8, RSDP_V1_SIZE);
+        if &bytes[0..8] != SIGNATURE || !checksum_valid(bytes) {
+            return None;
+        }
+
+        let revision = bytes[15];
+        let rsdt_address = read_u32(bytes, 16);
+
+        let xsdt_address = if revision >= 2 {
+            let bytes = core::slice::from_raw_parts(address as *const u8, RSDP_V2_SIZE);
+            let length = read_u32(bytes, 20) as usize;
+            let bytes = core::slice::from_raw_parts(address as *const u8, length.max(RSDP_V2_SIZE));
+            if !checksum_valid(bytes) {
+                return None;
+            }
+            Some(read_u64(bytes, 24)).filter(|address| *address != 0)
+        } else {
+            None
+        };
+
+        Some(Rsdp {
+            address,
+            source,
+            revision,
+            rsdt_address,
+            xsdt_address,
+        })
+    }
+}
@@ -0,0 +1,126 @@
+use core::fmt;
+
+use super::AcpiError;
+use crate::memory::mmio;
+use crate::memory::phys_addr::PhysAddr;
+
+pub const HEADER_SIZE: usize = 36;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub [u8; 4]);
+
+impl Signature {
+    pub const MADT: Signature = Signature(*b"APIC");
+    pub const FADT: Signature = Signature(*b"FACP");
+    pub const HPET: Signature = Signature(*b"HPET");
+    pub const MCFG: Signature = Signature(*b"MCFG");
+    pub const RSDT: Signature = Signature(*b"RSDT");
+    pub const XSDT: Signature = Signature(*b"XSDT");
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.0).unwrap_or("????")
+    }
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A system description table with a valid checksum.
+#[derive(Clone, Copy)]
+pub struct Sdt {
+    address: u64,
+    bytes: &'static [u8],
+}
+
+impl Sdt {
+    /// Maps the table at the given physical address and validates its checksum.
+    pub unsafe fn load(address: u64) -> Result<Sdt, AcpiError> {
+        let physical_address = PhysAddr::try_new(address)?;
+        mmio::identity_map(physical_address, HEADER_SIZE as u64)?;
+        let header = core::slice::from_raw_parts(address as *const u8, HEADER_SIZE);
+        let length = read_u32(header, 4) as usize;
+
+        if length < HEADER_SIZE {
+            return Err(AcpiError::InvalidLength(signature_of(header)));
+        }
+
+        mmio::identity_map(physical_address, length as u64)?;
+        let bytes = core::slice::from_raw_parts(address as *const u8, length);
+
+        if !checksum_valid(bytes) {
+            return Err(AcpiError::InvalidChecksum(signature_of(bytes)));
+        }
+
+        Ok(Sdt { address, bytes })
+    }
+
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    pub fn signature(&self) -> Signature {
+        signature_of(self.bytes)
+    }
+
+    pub fn length(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn revision(&self) -> u8 {
+        self.bytes[8]
+    }
+
+    /// The whole table including the header, so offsets match the specification.
+    pub fn bytes(&self) -> &'static [u8] {
+        self.bytes
+    }
+}
+
+impl fmt::Debug for Sdt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sdt")
+            .field("signature", &self.signature())
+            .field("address", &format_args!("{:#x}", self.address))
+            .field("length", &self.length())
+            .field("revision", &self.revision())
+            .finish()
+    }
+}
+
+fn signature_of(bytes: &[u8]) -> Signature {
+    Signature([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// All bytes of a table must sum up to zero.
+pub fn checksum_valid(bytes: &[u8]) -> bool {
+    bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) == 0
+}
+
+pub fn read_u8(bytes: &[u8], offset: usize) -> u8 {
+    bytes[offset]
+}
+
+pub fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+pub fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    let mut value = [0; 4];
+    value.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(value)
+}
+
+pub fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut value = [0; 8];
+    value.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(value)
+}
@@ -0,0 +1,113 @@
+use crate::memory::virt_addr::VirtAddr;
+
+const REGISTER_SELECT: u64 = 0x00;
+const REGISTER_WINDOW: u64 = 0x10;
+
+const REGISTER_VERSION: u32 = 0x01;
+const REGISTER_REDIRECTION_TABLE: u32 = 0x10;
+
+const REDIRECTION_ACTIVE_LOW: u64 = 1 << 13;
+const REDIRECTION_LEVEL_TRIGGERED: u64 = 1 << 15;
+const REDIRECTION_MASKED: u64 = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Redirection {
+    pub vector: u8,
+    /// APIC id of the processor which receives the interrupt.
+    pub destination: u8,
+    pub active_low: bool,
+    pub level_triggered: bool,
+    pub masked: bool,
+}
+
+impl Redirection {
+    fn bits(&self) -> u64 {
+        let mut bits = self.vector as u64 | (self.destination as u64) << 56;
+        if self.active_low {
+            bits |= REDIRECTION_ACTIVE_LOW;
+        }
+        if self.level_triggered {
+            bits |= REDIRECTION_LEVEL_TRIGGERED;
+        }
+        if self.masked {
+            bits |= REDIRECTION_MASKED;
+        }
+        bits
+    }
+}
+
+#[derive(Debug)]
+pub struct IoApic {
+    base: VirtAddr,
+    /// First global system interrupt handled by this I/O APIC.
+    gsi_base: u32,
+}
+
+impl IoApic {
+    /// The MMIO registers at `base` must be mapped.
+    pub unsafe fn new(base: VirtAddr, gsi_base: u32) -> Self {
+        IoApic { base, gsi_base }
+    }
+
+    /// Masks all redirection entries.
+    pub fn init(&mut self) {
+        for index in 0..self.redirection_entries() {
+            let entry = unsafe { self.read_redirection(index) };
+            unsafe { self.write_redirection(index, entry | REDIRECTION_MASKED) };
+        }
+    }
+
+    /// Reads of unmapped MMIO return all ones, which is no valid version register.
+    pub fn is_present(&self) -> bool {
+        unsafe { self.read(REGISTER_VERSION) != u32::MAX }
+    }
+
+    /// The version register holds the highest entry index, so up to 256 entries.
+    pub fn redirection_entries(&self) -> u16 {
+        unsafe { ((self.read(REGISTER_VERSION) >> 16) & 0xff) as u16 + 1 }
+    }
+
+    pub fn handles(&self, gsi: u32) -> bool {
+        (self.gsi_base..self.gsi_base + self.redirection_entries() as u32).contains(&gsi)
+    }
+
+    pub fn set_redirection(&mut self, gsi: u32, redirection: Redirection) {
+        let index = (gsi - self.gsi_base) as u16;
+        unsafe { self.write_redirection(index, redirection.bits()) };
+    }
+
+    pub fn set_masked(&mut self, gsi: u32, masked: bool) {
+        let index = (gsi - self.gsi_base) as u16;
+        unsafe {
+            let entry = self.read_redirection(index);
+            if masked {
+                self.write_redirection(index, entry | REDIRECTION_MASKED);
+            } else {
+                self.write_redirection(index, entry & !REDIRECTION_MASKED);
+            }
+        }
+    }
+
+    unsafe fn read_redirection(&self, index: u16) -> u64 {
+        let register = REGISTER_REDIRECTION_TABLE + 2 * index as u32;
+        self.read(register) as u64 | (self.read(register + 1) as u64) << 32
+    }
+
+    unsafe fn write_redirection(&mut self, index: u16, value: u64) {
+        let register = REGISTER_REDIRECTION_TABLE + 2 * index as u32;
+        // Keep the entry masked while it is half written
+        self.write(register, value as u32 | REDIRECTION_MASKED as u32);
+        self.write(register + 1, (value >> 32) as u32);
+        self.write(register, value as u32);
+    }
+
+    unsafe fn read(&self, register: u32) -> u32 {
+        core::ptr::write_volatile((self.base.as_u64() + REGISTER_SELECT) as *mut u32, register);
+        core::ptr::read_volatile((self.base.as_u64() + REGISTER_WINDOW) as *const u32)
+    }
+
+    unsafe fn write(&mut self, register: u32, value: u32) {
+        core::ptr::write_volatile((self.base.as_u64() + REGISTER_SELECT) as *mut u32, register);
+        core::ptr::write_volatile((self.base.as_u64() + REGISTER_WINDOW) as *mut u32, value);
+    }
+}
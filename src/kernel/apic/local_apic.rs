@@ -0,0 +1,106 @@
+use crate::asm::msr::{Msr, IA32_APIC_BASE};
+use crate::memory::virt_addr::VirtAddr;
+
+const APIC_BASE_ENABLE: u64 = 1 << 11;
+const APIC_BASE_X2APIC: u64 = 1 << 10;
+
+/// Base of the MSRs which mirror the xAPIC registers in x2APIC mode.
+const X2APIC_MSR_BASE: u32 = 0x800;
+
+// Register offsets in the xAPIC MMIO page
+const REGISTER_ID: u32 = 0x20;
+const REGISTER_VERSION: u32 = 0x30;
+const REGISTER_TASK_PRIORITY: u32 = 0x80;
+const REGISTER_END_OF_INTERRUPT: u32 = 0xb0;
+const REGISTER_SPURIOUS_VECTOR: u32 = 0xf0;
+const REGISTER_ERROR_STATUS: u32 = 0x280;
+const REGISTER_LVT_TIMER: u32 = 0x320;
+const REGISTER_LVT_LINT0: u32 = 0x350;
+const REGISTER_LVT_LINT1: u32 = 0x360;
+const REGISTER_LVT_ERROR: u32 = 0x370;
+
+const SPURIOUS_VECTOR_ENABLE: u32 = 1 << 8;
+const LVT_MASKED: u32 = 1 << 16;
+const LVT_DELIVERY_NMI: u32 = 0b100 << 8;
+
+#[derive(Debug, Clone, Copy)]
+pub enum LocalApicMode {
+    /// Registers are accessed through the MMIO page at the given address.
+    XApic(VirtAddr),
+    /// Registers are accessed through MSRs.
+    X2Apic,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LocalApic {
+    mode: LocalApicMode,
+}
+
+impl LocalApic {
+    /// The MMIO page must be mapped for the xAPIC mode.
+    pub unsafe fn new(mode: LocalApicMode) -> Self {
+        LocalApic { mode }
+    }
+
+    /// Enables the local APIC, masks all local interrupts except the NMI on LINT1
+    /// and delivers spurious interrupts to the given vector.
+    pub unsafe fn init(&self, spurious_vector: u8) {
+        let mut base = IA32_APIC_BASE.read() | APIC_BASE_ENABLE;
+        if let LocalApicMode::X2Apic = self.mode {
+            base |= APIC_BASE_X2APIC;
+        }
+        IA32_APIC_BASE.write(base);
+
+        self.write(REGISTER_TASK_PRIORITY, 0);
+        self.write(REGISTER_LVT_TIMER, LVT_MASKED);
+        self.write(REGISTER_LVT_LINT0, LVT_MASKED);
+        self.write(REGISTER_LVT_LINT1, LVT_DELIVERY_NMI);
+        self.write(REGISTER_LVT_ERROR, LVT_MASKED);
+
+        // The error status register must be written before it is read
+        self.write(REGISTER_ERROR_STATUS, 0);
+        self.write(REGISTER_ERROR_STATUS, 0);
+
+        self.write(
+            REGISTER_SPURIOUS_VECTOR,
+            SPURIOUS_VECTOR_ENABLE | spurious_vector as u32,
+        );
+        self.end_of_interrupt();
+    }
+
+    pub fn id(&self) -> u32 {
+        let id = unsafe { self.read(REGISTER_ID) };
+        match self.mode {
+            LocalApicMode::XApic(_) => id >> 24,
+            LocalApicMode::X2Apic => id,
+        }
+    }
+
+    pub fn version(&self) -> u8 {
+        unsafe { self.read(REGISTER_VERSION) as u8 }
+    }
+
+    pub fn end_of_interrupt(&self) {
+        unsafe { self.write(REGISTER_END_OF_INTERRUPT, 0) }
+    }
+
+    unsafe fn read(&self, register: u32) -> u32 {
+        match self.mode {
+            LocalApicMode::XApic(base) => {
+                core::ptr::read_volatile((base.as_u64() + register as u64) as *const u32)
+            }
+            LocalApicMode::X2Apic => Msr::new(X2APIC_MSR_BASE + (register >> 4)).read() as u32,
+        }
+    }
+
+    unsafe fn write(&self, register: u32, value: u32) {
+        match self.mode {
+            LocalApicMode::XApic(base) => {
+                core::ptr::write_volatile((base.as_u64() + register as u64) as *mut u32, value)
+            }
+            LocalApicMode::X2Apic => {
+                Msr::new(X2APIC_MSR_BASE + (register >> 4)).write(value as u64)
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+pub mod io_apic;
+pub mod local_apic;
+
+use alloc::vec::Vec;
+
+use self::io_apic::{IoApic, Redirection};
+use self::local_apic::{LocalApic, LocalApicMode};
+use crate::asm::cpuid::Features;
+use crate::memory::mmio;
+use crate::memory::paging::mapper::MapToError;
+use crate::memory::phys_addr::PhysAddr;
+use crate::pic::PIC_1_OFFSET;
+use crate::ylib::sync::mutex::Mutex;
+
+/// Vector the local APIC uses for spurious interrupts. Such an interrupt must
+/// not be acknowledged.
+pub const SPURIOUS_VECTOR: u8 = 0xff;
+
+/// Default addresses of the MultiProcessor specification.
+const DEFAULT_LOCAL_APIC_ADDRESS: u64 = 0xfee0_0000;
+const DEFAULT_IO_APIC_ADDRESS: u64 = 0xfec0_0000;
+
+const LOCAL_APIC_SIZE: u64 = 0x400;
+const IO_APIC_SIZE: u64 = 0x20;
+
+#[derive(Debug, Clone, Copy)]
+pub struct IoApicInfo {
+    pub id: u8,
+    pub address: PhysAddr,
+    /// First global system interrupt handled by this I/O APIC.
+    pub gsi_base: u32,
+}
+
+/// An ISA IRQ which is not connected to the I/O APIC input with the same number
+/// or which does not use the ISA default of an edge triggered, active high signal.
+#[derive(Debug, Clone, Copy)]
+pub struct InterruptOverride {
+    pub irq: u8,
+    pub gsi: u32,
+    pub active_low: bool,
+    pub level_triggered: bool,
+}
+
+/// Interrupt controllers of the platform, as described by the ACPI MADT.
+#[derive(Debug, Clone)]
+pub struct ApicTopology {
+    pub local_apic_address: PhysAddr,
+    pub io_apics: Vec<IoApicInfo>,
+    pub overrides: Vec<InterruptOverride>,
+}
+
+impl Default for ApicTopology {
+    /// A single I/O APIC at the default address with an identity mapping of the ISA IRQs.
+    fn default() -> Self {
+        ApicTopology {
+            local_apic_address: PhysAddr::new(DEFAULT_LOCAL_APIC_ADDRESS),
+            io_apics: alloc::vec![IoApicInfo {
+                id: 0,
+                address: PhysAddr::new(DEFAULT_IO_APIC_ADDRESS),
+                gsi_base: 0,
+            }],
+            overrides: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ApicError {
+    NotSupported,
+    NoIoApic,
+    /// An I/O APIC of the topology does not respond.
+    IoApicNotPresent,
+    Mapping,
+}
+
+impl From<MapToError> for ApicError {
+    fn from(_: MapToError) -> Self {
+        ApicError::Mapping
+    }
+}
+
+struct Apic {
+    local_apic: LocalApic,
+    io_apics: Vec<IoApic>,
+    overrides: Vec<InterruptOverride>,
+}
+
+static APIC: Mutex<Option<Apic>> = Mutex::new(None);
+
+pub fn is_supported() -> bool {
+    Features::read().has_apic()
+}
+
+/// Enables the local APIC of this processor and masks all I/O APIC inputs.
+/// The 8259 PICs must be masked before.
+///
+/// The local APIC is only enabled once every I/O APIC is usable, so the PICs
+/// still reach the processor through LINT0 if this fails.
+pub fn init(topology: ApicTopology) -> Result<(), ApicError> {
+    let features = Features::read();
+    if !features.has_apic() {
+        return Err(ApicError::NotSupported);
+    }
+    if topology.io_apics.is_empty() {
+        return Err(ApicError::NoIoApic);
+    }
+
+    let mode = if features.has_x2apic() {
+        LocalApicMode::X2Apic
+    } else {
+        LocalApicMode::XApic(mmio::identity_map(
+            topology.local_apic_address,
+            LOCAL_APIC_SIZE,
+        )?)
+    };
+
+    let mut io_apics = Vec::with_capacity(topology.io_apics.len());
+    for info in topology.io_apics.iter() {
+        let base = mmio::identity_map(info.address, IO_APIC_SIZE)?;
+        let io_apic = unsafe { IoApic::new(base, info.gsi_base) };
+        if !io_apic.is_present() {
+            return Err(ApicError::IoApicNotPresent);
+        }
+        io_apics.push(io_apic);
+    }
+    for io_apic in io_apics.iter_mut() {
+        io_apic.init();
+    }
+
+    let local_apic = unsafe { LocalApic::new(mode) };
+    unsafe { local_apic.init(SPURIOUS_VECTOR) };
+
+    ok!(
+        "Local APIC {} (version {:#x}) enabled in {} mode",
+        local_apic.id(),
+        local_apic.version(),
+        match mode {
+            LocalApicMode::XApic(_) => "xAPIC",
+            LocalApicMode::X2Apic => "x2APIC",
+        }
+    );
+    for (info, io_apic) in topology.io_apics.iter().zip(io_apics.iter()) {
+        ok!(
+            "I/O APIC {} at {:?} handles GSI {} to {}",
+            info.id,
+            info.address,
+            info.gsi_base,
+            info.gsi_base + io_apic.redirection_entries() as u32 - 1
+        );
+    }
+
+    crate::interrupts::disable_interrupts_for(|| {
+        *APIC.lock() = Some(Apic {
+            local_apic,
+            io_apics,
+            overrides: topology.overrides,
+        })
+    });
+    Ok(())
+}
+
+/// Routes the ISA IRQ to vector `PIC_1_OFFSET + irq`, so IRQ numbers are the
+/// same as with the 8259 PICs.
+pub fn unmask_irq(irq: u8) {
+    with_routing(irq, |local_apic, io_apic, routing| {
+        let redirection = Redirection {
+            vector: PIC_1_OFFSET + irq,
+            destination: local_apic.id() as u8,
+            active_low: routing.active_low,
+            level_triggered: routing.level_triggered,
+            masked: false,
+        };
+        io_apic.set_redirection(routing.gsi, redirection);
+    });
+}
+
+pub fn mask_irq(irq: u8) {
+    with_routing(irq, |_, io_apic, routing| {
+        io_apic.set_masked(routing.gsi, true)
+    });
+}
+
+pub fn end_of_interrupt() {
+    if let Some(apic) = APIC.lock().as_ref() {
+        apic.local_apic.end_of_interrupt();
+    }
+}
+
+fn with_routing<F>(irq: u8, func: F)
+where
+    F: FnOnce(&LocalApic, &mut IoApic, InterruptOverride),
+{
+    let mut apic = APIC.lock();
+    let apic = apic.as_mut().expect("APIC is not initialized");
+
+    let routing = apic
+        .overrides
+        .iter()
+        .find(|routing| routing.irq == irq)
+        .copied()
+        .unwrap_or(InterruptOverride {
+            irq,
+            gsi: irq as u32,
+            active_low: false,
+            level_triggered: false,
+        });
+
+    let io_apic = apic
+        .io_apics
+        .iter_mut()
+        .find(|io_apic| io_apic.handles(routing.gsi))
+        .expect("No I/O APIC handles the IRQ");
+
+    func(&apic.local_apic, io_apic, routing);
+}
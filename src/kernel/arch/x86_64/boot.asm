@@ -7,6 +7,10 @@ start:
     ; set stack pointer
     mov esp, stack_top
 
+    ; save pointer to the multiboot information structure, it is passed
+    ; as first argument (rdi) to _start
+    mov edi, ebx
+
     call check_multiboot ; Error code: 0
     call check_cpuid ; Error code: 1
     call check_long_mode ; Error code: 2
@@ -21,6 +25,12 @@ start:
     jmp gdt64.code:long_mode_start
 
 set_up_page_tables:
+    ; map last P4 entry to the P4 table itself (recursive mapping),
+    ; used by the rust paging code to access the page tables
+    mov eax, p4_table
+    or eax, 0b11 ; present + writable
+    mov [p4_table + 511 * 8], eax
+
     ; map first P4 entry to P3 table
     mov eax, p3_table
     or eax, 0b11 ; present + writable
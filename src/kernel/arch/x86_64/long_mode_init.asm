@@ -12,6 +12,10 @@ long_mode_start:
     mov fs, ax
     mov gs, ax
 
+    ; upper half of rdi is undefined after switching to long mode,
+    ; zero extend the multiboot information pointer
+    mov edi, edi
+
     ; call the rust main
     extern _start
     call _start
@@ -0,0 +1,42 @@
+use crate::memory::frame::Frame;
+use crate::memory::phys_addr::PhysAddr;
+use crate::memory::virt_addr::VirtAddr;
+
+/// Bits 12 to 51 of CR3 contain the physical address of the P4 table.
+const CR3_ADDRESS_MASK: u64 = 0x000f_ffff_ffff_f000;
+
+/// Contains the physical address of the active P4 table.
+pub struct Cr3;
+
+impl Cr3 {
+    /// Returns the frame of the active P4 table and the flags (PWT, PCD) of CR3.
+    pub fn read() -> (Frame, u64) {
+        let value: u64;
+        unsafe {
+            asm!("mov {}, cr3", out(reg) value, options(nomem, nostack));
+        }
+        let frame = Frame::containing_address(PhysAddr::new(value & CR3_ADDRESS_MASK));
+        (frame, value & !CR3_ADDRESS_MASK)
+    }
+
+    /// Loads a new P4 table. This flushes all non-global TLB entries.
+    ///
+    /// The caller must make sure that the new page table maps the kernel correctly.
+    pub unsafe fn write(frame: Frame, flags: u64) {
+        let value = frame.start_address().as_u64() | (flags & !CR3_ADDRESS_MASK);
+        asm!("mov cr3, {}", in(reg) value, options(nostack));
+    }
+}
+
+/// Contains the address which caused the last page fault.
+pub struct Cr2;
+
+impl Cr2 {
+    pub fn read() -> VirtAddr {
+        let value: u64;
+        unsafe {
+            asm!("mov {}, cr2", out(reg) value, options(nomem, nostack));
+        }
+        VirtAddr::new_truncate(value)
+    }
+}
@@ -0,0 +1,48 @@
+use core::arch::x86_64::{__cpuid, __cpuid_count, CpuidResult};
+
+pub fn cpuid(leaf: u32) -> CpuidResult {
+    unsafe { __cpuid(leaf) }
+}
+
+#[allow(dead_code)]
+pub fn cpuid_count(leaf: u32, sub_leaf: u32) -> CpuidResult {
+    unsafe { __cpuid_count(leaf, sub_leaf) }
+}
+
+/// Highest supported basic leaf.
+pub fn max_leaf() -> u32 {
+    cpuid(0).eax
+}
+
+/// Highest supported extended leaf (starting at 0x8000_0000).
+pub fn max_extended_leaf() -> u32 {
+    cpuid(0x8000_0000).eax
+}
+
+/// Feature flags of leaf 1.
+pub struct Features {
+    ecx: u32,
+    edx: u32,
+}
+
+impl Features {
+    pub fn read() -> Self {
+        let result = cpuid(1);
+        Features {
+            ecx: result.ecx,
+            edx: result.edx,
+        }
+    }
+
+    pub fn has_tsc(&self) -> bool {
+        self.edx & (1 << 4) != 0
+    }
+
+    pub fn has_apic(&self) -> bool {
+        self.edx & (1 << 9) != 0
+    }
+
+    pub fn has_x2apic(&self) -> bool {
+        self.ecx & (1 << 21) != 0
+    }
+}
@@ -0,0 +1,26 @@
+use crate::memory::virt_addr::VirtAddr;
+use crate::memory::DescriptorTablePointer;
+
+/// Location of the loaded GDT.
+pub fn sgdt() -> DescriptorTablePointer {
+    let mut pointer = DescriptorTablePointer {
+        limit: 0,
+        base: VirtAddr::zero(),
+    };
+    unsafe {
+        asm!("sgdt [{}]", in(reg) &mut pointer, options(nostack));
+    }
+    pointer
+}
+
+/// Location of the loaded IDT.
+pub fn sidt() -> DescriptorTablePointer {
+    let mut pointer = DescriptorTablePointer {
+        limit: 0,
+        base: VirtAddr::zero(),
+    };
+    unsafe {
+        asm!("sidt [{}]", in(reg) &mut pointer, options(nostack));
+    }
+    pointer
+}
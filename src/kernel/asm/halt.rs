@@ -5,3 +5,11 @@ pub fn halt_loop() -> ! {
         }
     }
 }
+
+/// Enables interrupts and halts until the next one. No interrupt can slip in
+/// between, because `sti` only takes effect after the following instruction.
+pub fn enable_interrupts_and_halt() {
+    unsafe {
+        asm!("sti", "hlt");
+    }
+}
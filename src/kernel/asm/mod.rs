@@ -1,7 +1,12 @@
 pub mod breakpoint;
+pub mod control_registers;
+pub mod cpuid;
+pub mod descriptor_tables;
 pub mod flags;
 pub mod halt;
 pub mod interrupts;
+pub mod msr;
 pub mod port;
+pub mod tlb;
 
 pub use port::Port;
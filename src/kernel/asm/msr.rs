@@ -0,0 +1,26 @@
+/// A model specific register.
+#[derive(Debug, Clone, Copy)]
+pub struct Msr {
+    register: u32,
+}
+
+impl Msr {
+    pub const fn new(register: u32) -> Self {
+        Msr { register }
+    }
+
+    pub unsafe fn read(&self) -> u64 {
+        let (high, low): (u32, u32);
+        asm!("rdmsr", in("ecx") self.register, out("eax") low, out("edx") high, options(nomem, nostack));
+        ((high as u64) << 32) | low as u64
+    }
+
+    pub unsafe fn write(&self, value: u64) {
+        let low = value as u32;
+        let high = (value >> 32) as u32;
+        asm!("wrmsr", in("ecx") self.register, in("eax") low, in("edx") high, options(nostack));
+    }
+}
+
+/// Physical base address and enable bits of the local APIC.
+pub const IA32_APIC_BASE: Msr = Msr::new(0x1b);
@@ -25,4 +25,24 @@ impl Port {
         asm!("in al, dx", out("al") _result, in("dx") self.address + offset as u16);
         _result
     }
+
+    pub unsafe fn write_u16(&self, value: u16) {
+        asm!("out dx, ax", in("dx") self.address, in("ax") value);
+    }
+
+    pub unsafe fn read_u16(&self) -> u16 {
+        let mut _result: u16 = 0;
+        asm!("in ax, dx", out("ax") _result, in("dx") self.address);
+        _result
+    }
+
+    pub unsafe fn write_u32(&self, value: u32) {
+        asm!("out dx, eax", in("dx") self.address, in("eax") value);
+    }
+
+    pub unsafe fn read_u32(&self) -> u32 {
+        let mut _result: u32 = 0;
+        asm!("in eax, dx", out("eax") _result, in("dx") self.address);
+        _result
+    }
 }
@@ -0,0 +1,18 @@
+use crate::memory::virt_addr::VirtAddr;
+
+use super::control_registers::Cr3;
+
+/// Invalidates the TLB entry of the page containing the given address.
+#[allow(dead_code)]
+pub fn flush(address: VirtAddr) {
+    unsafe {
+        asm!("invlpg [{}]", in(reg) address.as_u64(), options(nostack));
+    }
+}
+
+/// Invalidates all non-global TLB entries by reloading CR3.
+#[allow(dead_code)]
+pub fn flush_all() {
+    let (frame, flags) = Cr3::read();
+    unsafe { Cr3::write(frame, flags) }
+}
@@ -0,0 +1,141 @@
+pub mod tsc;
+
+use core::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, Ordering};
+use core::time::Duration;
+
+use crate::multiboot::command_line;
+use crate::{hpet, pit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ClockSource {
+    /// Ticks of the PIT, so the resolution is one tick.
+    Pit = 0,
+    /// The invariant TSC with nanosecond resolution.
+    Tsc = 1,
+    /// The HPET main counter, usually with a resolution of 10 to 100 ns.
+    Hpet = 2,
+}
+
+/// Timer which raises IRQ0 periodically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TickSource {
+    Pit = 0,
+    Hpet = 1,
+}
+
+static SOURCE: AtomicU8 = AtomicU8::new(ClockSource::Pit as u8);
+static TICK_SOURCE: AtomicU8 = AtomicU8::new(TickSource::Pit as u8);
+/// Frequency of the HPET tick timer.
+static TICK_FREQUENCY: AtomicU32 = AtomicU32::new(0);
+/// Value of the clock source at the start of the clock.
+static START: AtomicU64 = AtomicU64::new(0);
+/// Frequency of the TSC in Hz.
+static TSC_FREQUENCY: AtomicU64 = AtomicU64::new(0);
+
+/// Reads the tick source from the kernel command line (`tick=pit|hpet`).
+pub fn tick_source_from_command_line(command_line: Option<&str>) -> TickSource {
+    match command_line.and_then(|command_line| command_line::argument(command_line, "tick")) {
+        Some("hpet") => TickSource::Hpet,
+        _ => TickSource::Pit,
+    }
+}
+
+/// Starts the periodic timer interrupt, falling back to the PIT if the HPET
+/// can not be used.
+pub fn start_ticks(source: TickSource, frequency: u32) {
+    if source == TickSource::Hpet {
+        match hpet::start_tick(frequency) {
+            Ok(()) => {
+                TICK_SOURCE.store(TickSource::Hpet as u8, Ordering::SeqCst);
+                TICK_FREQUENCY.store(frequency, Ordering::SeqCst);
+                return;
+            }
+            Err(error) => serial_println!("HPET can not be used as tick source: {:?}", error),
+        }
+    }
+    pit::init(frequency);
+    TICK_SOURCE.store(TickSource::Pit as u8, Ordering::SeqCst);
+}
+
+/// Frequency of the tick source in Hz. The PIT is asked every time, because
+/// its frequency can be changed with `pit::set_frequency`.
+pub fn tick_frequency() -> u32 {
+    match tick_source() {
+        TickSource::Pit => pit::frequency(),
+        TickSource::Hpet => TICK_FREQUENCY.load(Ordering::SeqCst),
+    }
+}
+
+pub fn tick_source() -> TickSource {
+    match TICK_SOURCE.load(Ordering::SeqCst) {
+        1 => TickSource::Hpet,
+        _ => TickSource::Pit,
+    }
+}
+
+/// Number of timer interrupts of the tick source.
+pub fn ticks() -> u64 {
+    match tick_source() {
+        TickSource::Pit => pit::ticks(),
+        TickSource::Hpet => hpet::ticks(),
+    }
+}
+
+/// Uses the TSC as clock if it is invariant, the HPET if it has a 64 bit
+/// counter and the PIT otherwise. The tick source must be started before.
+pub fn init() {
+    // Continue where the PIT clock is, so the clock never goes backwards
+    let now = pit::uptime().as_nanos() as u64;
+    let hpet_start = hpet::nanoseconds().and_then(|hpet_now| hpet_now.checked_sub(now));
+
+    if tsc::is_available() && tsc::is_invariant() {
+        let frequency = tsc::frequency_from_cpuid()
+            .or_else(tsc::calibrate_with_hpet)
+            .unwrap_or_else(tsc::calibrate_with_pit);
+
+        let offset = now as u128 * frequency as u128 / 1_000_000_000;
+        TSC_FREQUENCY.store(frequency, Ordering::SeqCst);
+        START.store(tsc::read() - offset as u64, Ordering::SeqCst);
+        SOURCE.store(ClockSource::Tsc as u8, Ordering::SeqCst);
+
+        ok!(
+            "Using the invariant TSC with {}.{:03} MHz as clock",
+            frequency / 1_000_000,
+            frequency / 1_000 % 1_000
+        );
+    } else if let Some(hpet_start) = hpet_start {
+        START.store(hpet_start, Ordering::SeqCst);
+        SOURCE.store(ClockSource::Hpet as u8, Ordering::SeqCst);
+        ok!("TSC is not invariant, using the HPET as clock");
+    } else {
+        ok!("TSC is not invariant, using the PIT as clock");
+    }
+}
+
+pub fn source() -> ClockSource {
+    match SOURCE.load(Ordering::SeqCst) {
+        1 => ClockSource::Tsc,
+        2 => ClockSource::Hpet,
+        _ => ClockSource::Pit,
+    }
+}
+
+/// Nanoseconds since the clock was started. Never goes backwards.
+pub fn monotonic_ns() -> u64 {
+    match source() {
+        ClockSource::Tsc => {
+            let cycles = tsc::read() - START.load(Ordering::SeqCst);
+            (cycles as u128 * 1_000_000_000 / TSC_FREQUENCY.load(Ordering::SeqCst) as u128) as u64
+        }
+        ClockSource::Hpet => hpet::nanoseconds()
+            .unwrap()
+            .saturating_sub(START.load(Ordering::SeqCst)),
+        ClockSource::Pit => pit::uptime().as_nanos() as u64,
+    }
+}
+
+pub fn uptime() -> Duration {
+    Duration::from_nanos(monotonic_ns())
+}
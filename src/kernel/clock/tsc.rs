@@ -0,0 +1,70 @@
+use core::arch::x86_64::_rdtsc;
+use core::time::Duration;
+
+use crate::asm::cpuid::{self, Features};
+use crate::{hpet, pit};
+
+/// Leaf with the advanced power management flags.
+const LEAF_POWER_MANAGEMENT: u32 = 0x8000_0007;
+const INVARIANT_TSC: u32 = 1 << 8;
+/// Leaf with the ratio of the TSC to the core crystal clock.
+const LEAF_TSC_CRYSTAL_RATIO: u32 = 0x15;
+
+const CALIBRATION_MS: u64 = 10;
+const CALIBRATION_RUNS: usize = 5;
+
+pub fn read() -> u64 {
+    unsafe { _rdtsc() }
+}
+
+pub fn is_available() -> bool {
+    Features::read().has_tsc()
+}
+
+/// An invariant TSC runs at a constant rate in all power states, so it can
+/// be used as a clock.
+pub fn is_invariant() -> bool {
+    cpuid::max_extended_leaf() >= LEAF_POWER_MANAGEMENT
+        && cpuid::cpuid(LEAF_POWER_MANAGEMENT).edx & INVARIANT_TSC != 0
+}
+
+/// Frequency in Hz as reported by the processor, which most processors
+/// (and hypervisors) do not do.
+pub fn frequency_from_cpuid() -> Option<u64> {
+    if cpuid::max_leaf() < LEAF_TSC_CRYSTAL_RATIO {
+        return None;
+    }
+
+    let result = cpuid::cpuid(LEAF_TSC_CRYSTAL_RATIO);
+    let (denominator, numerator, crystal_frequency) = (result.eax, result.ebx, result.ecx);
+    if denominator == 0 || numerator == 0 || crystal_frequency == 0 {
+        return None;
+    }
+    Some(crystal_frequency as u64 * numerator as u64 / denominator as u64)
+}
+
+/// Measures the frequency in Hz against a busy wait of the PIT. The shortest
+/// of several runs is used, because polling the PIT only ever adds time.
+pub fn calibrate_with_pit() -> u64 {
+    let cycles = (0..CALIBRATION_RUNS)
+        .map(|_| {
+            let start = read();
+            pit::sleep_ms(CALIBRATION_MS);
+            read() - start
+        })
+        .min()
+        .unwrap();
+    cycles * 1000 / CALIBRATION_MS
+}
+
+/// Measures the frequency in Hz against the HPET main counter, which is
+/// read at the start and the end, so the busy wait does not add an error.
+pub fn calibrate_with_hpet() -> Option<u64> {
+    let hpet_start = hpet::counter()?;
+    let start = read();
+    hpet::sleep(Duration::from_millis(CALIBRATION_MS));
+    let elapsed = hpet::elapsed(hpet_start);
+    let end = read();
+
+    Some(((end - start) as u128 * 1_000_000_000 / elapsed.as_nanos().max(1)) as u64)
+}
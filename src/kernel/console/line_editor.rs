@@ -0,0 +1,128 @@
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const MAX_LINE_LENGTH: usize = 256;
+const MAX_HISTORY: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKey {
+    Insert(char),
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    HistoryPrevious,
+    HistoryNext,
+    /// Removes everything before the cursor.
+    KillToStart,
+    /// Removes everything from the cursor on.
+    KillToEnd,
+    Enter,
+}
+
+/// The line being edited and the previously entered lines. It only holds the
+/// text, drawing it is up to the console.
+pub struct LineEditor {
+    line: Vec<char>,
+    cursor: usize,
+    history: VecDeque<String>,
+    /// Entry of the history which is shown, `None` while editing a new line.
+    history_index: Option<usize>,
+    /// The new line while browsing the history.
+    draft: Vec<char>,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        LineEditor {
+            line: Vec::new(),
+            cursor: 0,
+            history: VecDeque::new(),
+            history_index: None,
+            draft: Vec::new(),
+        }
+    }
+
+    pub fn line(&self) -> &[char] {
+        &self.line
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Applies the key. Returns the line once it is entered.
+    pub fn handle(&mut self, key: EditKey) -> Option<String> {
+        match key {
+            EditKey::Insert(character) => {
+                if self.line.len() < MAX_LINE_LENGTH {
+                    self.line.insert(self.cursor, character);
+                    self.cursor += 1;
+                }
+            }
+            EditKey::Backspace => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.line.remove(self.cursor);
+                }
+            }
+            EditKey::Delete => {
+                if self.cursor < self.line.len() {
+                    self.line.remove(self.cursor);
+                }
+            }
+            EditKey::Left => self.cursor = self.cursor.saturating_sub(1),
+            EditKey::Right => self.cursor = (self.cursor + 1).min(self.line.len()),
+            EditKey::Home => self.cursor = 0,
+            EditKey::End => self.cursor = self.line.len(),
+            EditKey::HistoryPrevious => self.browse_history(true),
+            EditKey::HistoryNext => self.browse_history(false),
+            EditKey::KillToStart => {
+                self.line.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            EditKey::KillToEnd => self.line.truncate(self.cursor),
+            EditKey::Enter => return Some(self.finish_line()),
+        }
+        None
+    }
+
+    fn finish_line(&mut self) -> String {
+        let line: String = self.line.drain(..).collect();
+        self.cursor = 0;
+        self.history_index = None;
+        self.draft.clear();
+
+        if !line.trim().is_empty() && self.history.back() != Some(&line) {
+            if self.history.len() == MAX_HISTORY {
+                self.history.pop_front();
+            }
+            self.history.push_back(line.clone());
+        }
+        line
+    }
+
+    /// Index 0 is the oldest entry.
+    fn browse_history(&mut self, older: bool) {
+        let index = match (self.history_index, older) {
+            (None, true) if !self.history.is_empty() => {
+                self.draft = self.line.clone();
+                Some(self.history.len() - 1)
+            }
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index + 1 < self.history.len() => Some(index + 1),
+            (Some(_), false) => None,
+            _ => return,
+        };
+
+        self.history_index = index;
+        self.line = match index {
+            Some(index) => self.history[index].chars().collect(),
+            None => core::mem::take(&mut self.draft),
+        };
+        self.cursor = self.line.len();
+    }
+}
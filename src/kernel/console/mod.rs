@@ -0,0 +1,162 @@
+use alloc::string::String;
+use core::fmt;
+
+use self::line_editor::{EditKey, LineEditor};
+use self::serial_input::SerialDecoder;
+use crate::keyboard::{self, event::KeyEvent, key_code::KeyCode};
+use crate::vga_buffer::{BUFFER_WIDTH, WRITER};
+use crate::ylib::sync::{lazy::Lazy, mutex::Mutex};
+
+pub mod line_editor;
+pub mod serial_input;
+
+/// Columns left for the input after the prompt, otherwise it starts on a new line.
+const MIN_INPUT_WIDTH: usize = 20;
+
+/// Keeps the history between the lines.
+static EDITOR: Lazy<Mutex<LineEditor>, fn() -> Mutex<LineEditor>> =
+    Lazy::new(|| Mutex::new(LineEditor::new()));
+static SERIAL_DECODER: Mutex<SerialDecoder> = Mutex::new(SerialDecoder::new());
+
+/// Prints to the screen and to COM1.
+#[macro_export]
+macro_rules! console_print {
+    ($($arg:tt)*) => ($crate::console::_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! console_println {
+    () => ($crate::console_print!("\n"));
+    ($($arg:tt)*) => ($crate::console_print!("{}\n", format_args!($($arg)*)));
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    crate::vga_buffer::_print(args);
+    crate::serial::_serial_print(args);
+}
+
+/// Shows the prompt and lets the user edit a line until Enter is pressed.
+/// Keys are taken from the keyboard and from COM1, the line is shown on the
+/// screen and on COM1. Lines longer than the screen scroll horizontally.
+pub fn read_line(prompt: &str) -> String {
+    console_print!("{}", prompt);
+    let start = crate::interrupts::disable_interrupts_for(|| {
+        let mut writer = WRITER.lock();
+        if writer.column() + MIN_INPUT_WIDTH > BUFFER_WIDTH {
+            writer.write_char('\n');
+        }
+        writer.column()
+    });
+
+    let mut editor = EDITOR.lock();
+    // First character of the line which is visible on the screen
+    let mut view = 0;
+    loop {
+        let key = match wait_for_edit_key() {
+            Some(key) => key,
+            None => continue,
+        };
+        if let Some(line) = editor.handle(key) {
+            console_println!();
+            return line;
+        }
+        view = draw(&editor, start, view);
+        draw_serial(&editor, prompt);
+    }
+}
+
+/// Draws the line from the start column and returns the new view.
+fn draw(editor: &LineEditor, start: usize, view: usize) -> usize {
+    // The last column is kept free for the cursor behind the last character
+    let width = BUFFER_WIDTH - start - 1;
+    let line = editor.line();
+    let cursor = editor.cursor();
+
+    let mut view = view.min(line.len().saturating_sub(width));
+    if cursor < view {
+        view = cursor;
+    } else if cursor - view > width {
+        view = cursor - width;
+    }
+
+    crate::interrupts::disable_interrupts_for(|| {
+        let mut writer = WRITER.lock();
+        writer.set_column(start);
+        for &character in line[view..].iter().take(width) {
+            writer.write_char(character);
+        }
+        writer.clear_to_end_of_line();
+        writer.set_column(start + cursor - view);
+    });
+    view
+}
+
+/// Draws the whole line again, the terminal wraps long lines by itself.
+fn draw_serial(editor: &LineEditor, prompt: &str) {
+    let line: String = editor.line().iter().collect();
+    // Carriage return, the line, erase to the end of the line
+    serial_print!("\r{}{}\x1b[K", prompt, line);
+    let behind_cursor = editor.line().len() - editor.cursor();
+    if behind_cursor > 0 {
+        serial_print!("\x1b[{}D", behind_cursor);
+    }
+}
+
+/// Runs expired timers and sleeps until the next interrupt while there is no input.
+fn wait_for_edit_key() -> Option<EditKey> {
+    loop {
+        if let Some(event) = keyboard::read_event() {
+            if event.pressed {
+                return edit_key(&event);
+            }
+            continue;
+        }
+        if let Some(byte) = crate::serial::read_byte() {
+            return SERIAL_DECODER.lock().add_byte(byte);
+        }
+
+        crate::timer::run_expired();
+
+        crate::asm::interrupts::disable_interrupts();
+        if keyboard::has_event() || crate::serial::has_input() || crate::timer::has_pending() {
+            crate::asm::interrupts::enable_interrupts();
+        } else {
+            crate::asm::halt::enable_interrupts_and_halt();
+        }
+    }
+}
+
+fn edit_key(event: &KeyEvent) -> Option<EditKey> {
+    let ctrl = event.modifiers.ctrl();
+    // The keypad works as cursor keys while num lock is off
+    let keypad = event.char.is_none();
+
+    let key = match event.key {
+        KeyCode::Enter | KeyCode::KeypadEnter => EditKey::Enter,
+        KeyCode::Backspace => EditKey::Backspace,
+        KeyCode::Delete => EditKey::Delete,
+        KeyCode::KeypadPeriod if keypad => EditKey::Delete,
+        KeyCode::Left => EditKey::Left,
+        KeyCode::Keypad4 if keypad => EditKey::Left,
+        KeyCode::Right => EditKey::Right,
+        KeyCode::Keypad6 if keypad => EditKey::Right,
+        KeyCode::Up => EditKey::HistoryPrevious,
+        KeyCode::Keypad8 if keypad => EditKey::HistoryPrevious,
+        KeyCode::Down => EditKey::HistoryNext,
+        KeyCode::Keypad2 if keypad => EditKey::HistoryNext,
+        KeyCode::Home => EditKey::Home,
+        KeyCode::Keypad7 if keypad => EditKey::Home,
+        KeyCode::End => EditKey::End,
+        KeyCode::Keypad1 if keypad => EditKey::End,
+        KeyCode::A if ctrl => EditKey::Home,
+        KeyCode::E if ctrl => EditKey::End,
+        KeyCode::U if ctrl => EditKey::KillToStart,
+        KeyCode::K if ctrl => EditKey::KillToEnd,
+        _ => match event.char {
+            Some(character) if !ctrl && !character.is_control() => EditKey::Insert(character),
+            _ => return None,
+        },
+    };
+    Some(key)
+}
@@ -0,0 +1,93 @@
+use super::line_editor::EditKey;
+
+const ESCAPE: u8 = 0x1b;
+const DELETE: u8 = 0x7f;
+const BACKSPACE: u8 = 0x08;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    Escape,
+    /// Control sequence with its numeric parameter so far, e.g. `ESC [ 3 ~`.
+    ControlSequence(u8),
+}
+
+/// Turns the bytes of a VT100 compatible terminal into edit keys, including
+/// the escape sequences of the cursor keys and the control characters of
+/// common line editors.
+pub struct SerialDecoder {
+    state: State,
+    /// Terminals send either CR, LF or CR LF for Enter.
+    last_was_carriage_return: bool,
+}
+
+impl SerialDecoder {
+    pub const fn new() -> Self {
+        SerialDecoder {
+            state: State::Start,
+            last_was_carriage_return: false,
+        }
+    }
+
+    pub fn add_byte(&mut self, byte: u8) -> Option<EditKey> {
+        let last_was_carriage_return = self.last_was_carriage_return;
+        self.last_was_carriage_return = byte == b'\r';
+
+        match self.state {
+            State::Start => match byte {
+                ESCAPE => {
+                    self.state = State::Escape;
+                    None
+                }
+                b'\r' => Some(EditKey::Enter),
+                b'\n' if last_was_carriage_return => None,
+                b'\n' => Some(EditKey::Enter),
+                DELETE | BACKSPACE => Some(EditKey::Backspace),
+                // Ctrl + A, E, B, F, P, N, D, U, K
+                0x01 => Some(EditKey::Home),
+                0x05 => Some(EditKey::End),
+                0x02 => Some(EditKey::Left),
+                0x06 => Some(EditKey::Right),
+                0x10 => Some(EditKey::HistoryPrevious),
+                0x0e => Some(EditKey::HistoryNext),
+                0x04 => Some(EditKey::Delete),
+                0x15 => Some(EditKey::KillToStart),
+                0x0b => Some(EditKey::KillToEnd),
+                0x20..=0x7e => Some(EditKey::Insert(byte as char)),
+                _ => None,
+            },
+            State::Escape => {
+                self.state = match byte {
+                    b'[' | b'O' => State::ControlSequence(0),
+                    _ => State::Start,
+                };
+                None
+            }
+            State::ControlSequence(parameter) => match byte {
+                b'0'..=b'9' => {
+                    let parameter = parameter.saturating_mul(10).saturating_add(byte - b'0');
+                    self.state = State::ControlSequence(parameter);
+                    None
+                }
+                // Parameters of modifier keys are ignored
+                b';' => {
+                    self.state = State::ControlSequence(0);
+                    None
+                }
+                _ => {
+                    self.state = State::Start;
+                    match (byte, parameter) {
+                        (b'A', _) => Some(EditKey::HistoryPrevious),
+                        (b'B', _) => Some(EditKey::HistoryNext),
+                        (b'C', _) => Some(EditKey::Right),
+                        (b'D', _) => Some(EditKey::Left),
+                        (b'H', _) | (b'~', 1) | (b'~', 7) => Some(EditKey::Home),
+                        (b'F', _) | (b'~', 4) | (b'~', 8) => Some(EditKey::End),
+                        (b'~', 3) => Some(EditKey::Delete),
+                        _ => None,
+                    }
+                }
+            },
+        }
+    }
+}
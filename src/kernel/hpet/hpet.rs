@@ -0,0 +1,194 @@
+use crate::memory::paging::mapper::MapToError;
+use crate::memory::virt_addr::VirtAddr;
+
+const REGISTER_CAPABILITIES: u64 = 0x000;
+const REGISTER_CONFIGURATION: u64 = 0x010;
+const REGISTER_MAIN_COUNTER: u64 = 0x0f0;
+
+const CAPABILITY_COUNTER_64BIT: u64 = 1 << 13;
+const CAPABILITY_LEGACY_REPLACEMENT: u64 = 1 << 15;
+
+const CONFIGURATION_ENABLE: u64 = 1 << 0;
+const CONFIGURATION_LEGACY_REPLACEMENT: u64 = 1 << 1;
+
+const TIMER_INTERRUPT_ENABLE: u64 = 1 << 2;
+const TIMER_PERIODIC: u64 = 1 << 3;
+const TIMER_PERIODIC_CAPABLE: u64 = 1 << 4;
+const TIMER_VALUE_SET: u64 = 1 << 6;
+const TIMER_ROUTE_SHIFT: u64 = 9;
+const TIMER_ROUTE_MASK: u64 = 0b1_1111 << TIMER_ROUTE_SHIFT;
+
+/// Size of the register block, which has room for 32 timers.
+pub const REGISTER_BLOCK_SIZE: u64 = 0x400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HpetError {
+    /// There is no ACPI HPET table.
+    NotFound,
+    /// The registers are not in the memory address space.
+    NotMemoryMapped,
+    InvalidTimer(u8),
+    PeriodicNotSupported(u8),
+    /// The timer can not be connected to this I/O APIC input.
+    RouteNotSupported {
+        timer: u8,
+        irq: u8,
+    },
+    LegacyReplacementNotSupported,
+    Mapping(MapToError),
+}
+
+/// The High Precision Event Timer: a free running main counter and a set of
+/// comparators which raise an interrupt when the counter reaches them.
+#[derive(Debug)]
+pub struct Hpet {
+    base: VirtAddr,
+}
+
+impl Hpet {
+    /// The register block at `base` must be mapped.
+    pub unsafe fn new(base: VirtAddr) -> Self {
+        Hpet { base }
+    }
+
+    /// The main counter can be read at this address without side effects.
+    pub fn counter_address(&self) -> VirtAddr {
+        VirtAddr::new(self.base.as_u64() + REGISTER_MAIN_COUNTER)
+    }
+
+    /// Length of one counter tick in femtoseconds.
+    pub fn period_fs(&self) -> u64 {
+        self.capabilities() >> 32
+    }
+
+    pub fn timer_count(&self) -> u8 {
+        ((self.capabilities() >> 8) & 0b1_1111) as u8 + 1
+    }
+
+    pub fn has_64bit_counter(&self) -> bool {
+        self.capabilities() & CAPABILITY_COUNTER_64BIT != 0
+    }
+
+    /// Timer 0 and 1 can replace the PIT on IRQ0 and the RTC on IRQ8.
+    pub fn supports_legacy_replacement(&self) -> bool {
+        self.capabilities() & CAPABILITY_LEGACY_REPLACEMENT != 0
+    }
+
+    /// Starts the main counter.
+    pub fn enable(&mut self) {
+        unsafe {
+            let configuration = self.read(REGISTER_CONFIGURATION);
+            self.write(REGISTER_CONFIGURATION, configuration | CONFIGURATION_ENABLE);
+        }
+    }
+
+    pub fn set_legacy_replacement(&mut self, enabled: bool) -> Result<(), HpetError> {
+        if enabled && !self.supports_legacy_replacement() {
+            return Err(HpetError::LegacyReplacementNotSupported);
+        }
+        unsafe {
+            let configuration = self.read(REGISTER_CONFIGURATION);
+            if enabled {
+                self.write(
+                    REGISTER_CONFIGURATION,
+                    configuration | CONFIGURATION_LEGACY_REPLACEMENT,
+                );
+            } else {
+                self.write(
+                    REGISTER_CONFIGURATION,
+                    configuration & !CONFIGURATION_LEGACY_REPLACEMENT,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn counter(&self) -> u64 {
+        unsafe { self.read(REGISTER_MAIN_COUNTER) }
+    }
+
+    /// Bit n is set if the timer can be routed to I/O APIC input n.
+    pub fn route_capabilities(&self, timer: u8) -> Result<u32, HpetError> {
+        let configuration = unsafe { self.read(self.timer_configuration(timer)?) };
+        Ok((configuration >> 32) as u32)
+    }
+
+    /// Connects the timer to an I/O APIC input. Not used for timer 0 and 1
+    /// while the legacy replacement route is enabled.
+    pub fn set_route(&mut self, timer: u8, irq: u8) -> Result<(), HpetError> {
+        if irq >= 32 || self.route_capabilities(timer)? & (1 << irq) == 0 {
+            return Err(HpetError::RouteNotSupported { timer, irq });
+        }
+        let register = self.timer_configuration(timer)?;
+        unsafe {
+            let configuration = self.read(register) & !TIMER_ROUTE_MASK;
+            self.write(register, configuration | (irq as u64) << TIMER_ROUTE_SHIFT);
+        }
+        Ok(())
+    }
+
+    /// Raises one interrupt `ticks` counter ticks from now.
+    pub fn set_one_shot(&mut self, timer: u8, ticks: u64) -> Result<(), HpetError> {
+        let register = self.timer_configuration(timer)?;
+        unsafe {
+            let configuration = self.read(register) & !TIMER_PERIODIC;
+            self.write(register, configuration | TIMER_INTERRUPT_ENABLE);
+            self.write(register + 8, self.counter().wrapping_add(ticks));
+        }
+        Ok(())
+    }
+
+    /// Raises an interrupt every `ticks` counter ticks.
+    pub fn set_periodic(&mut self, timer: u8, ticks: u64) -> Result<(), HpetError> {
+        let register = self.timer_configuration(timer)?;
+        unsafe {
+            let configuration = self.read(register);
+            if configuration & TIMER_PERIODIC_CAPABLE == 0 {
+                return Err(HpetError::PeriodicNotSupported(timer));
+            }
+
+            // With the value set bit, the first write sets the comparator
+            // and the second write the period which is added after each interrupt
+            self.write(
+                register,
+                configuration | TIMER_INTERRUPT_ENABLE | TIMER_PERIODIC | TIMER_VALUE_SET,
+            );
+            self.write(register + 8, self.counter().wrapping_add(ticks));
+            self.write(register + 8, ticks);
+        }
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn disable_timer(&mut self, timer: u8) -> Result<(), HpetError> {
+        let register = self.timer_configuration(timer)?;
+        unsafe {
+            let configuration = self.read(register);
+            self.write(
+                register,
+                configuration & !(TIMER_INTERRUPT_ENABLE | TIMER_PERIODIC),
+            );
+        }
+        Ok(())
+    }
+
+    fn capabilities(&self) -> u64 {
+        unsafe { self.read(REGISTER_CAPABILITIES) }
+    }
+
+    /// Offset of the configuration register of the timer, its comparator follows.
+    fn timer_configuration(&self, timer: u8) -> Result<u64, HpetError> {
+        if timer >= self.timer_count() {
+            return Err(HpetError::InvalidTimer(timer));
+        }
+        Ok(0x100 + 0x20 * timer as u64)
+    }
+
+    unsafe fn read(&self, register: u64) -> u64 {
+        core::ptr::read_volatile((self.base.as_u64() + register) as *const u64)
+    }
+
+    unsafe fn write(&mut self, register: u64, value: u64) {
+        core::ptr::write_volatile((self.base.as_u64() + register) as *mut u64, value)
+    }
+}
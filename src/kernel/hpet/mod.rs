@@ -0,0 +1,191 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+use self::hpet::{Hpet, HpetError, REGISTER_BLOCK_SIZE};
+use crate::acpi::generic_address::AddressSpace;
+use crate::interrupts::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::interrupts::irq::{self, IrqReturn};
+use crate::memory::mmio;
+use crate::memory::phys_addr::PhysAddr;
+use crate::ylib::sync::mutex::Mutex;
+
+pub mod hpet;
+
+/// Timer which replaces the PIT on IRQ0 in legacy replacement mode.
+const TICK_TIMER: u8 = 0;
+const FEMTOSECONDS_PER_NANOSECOND: u64 = 1_000_000;
+
+pub static HPET: Mutex<Option<Hpet>> = Mutex::new(None);
+
+/// Address of the main counter and its period, so the counter can be read
+/// without the lock (e.g. from interrupt handlers).
+static COUNTER_ADDRESS: AtomicU64 = AtomicU64::new(0);
+static PERIOD_FS: AtomicU64 = AtomicU64::new(0);
+/// Valid bits of the main counter, a 32 bit counter wraps after a few minutes.
+static COUNTER_MASK: AtomicU64 = AtomicU64::new(u64::MAX);
+static TICKS: AtomicU64 = AtomicU64::new(0);
+
+/// Maps the HPET described by the ACPI HPET table and starts its main counter.
+pub fn init() -> Result<(), HpetError> {
+    let table = crate::acpi::tables()
+        .and_then(|tables| tables.hpet)
+        .ok_or(HpetError::NotFound)?;
+    if table.base_address.address_space != AddressSpace::SystemMemory {
+        return Err(HpetError::NotMemoryMapped);
+    }
+
+    let base = mmio::identity_map(
+        PhysAddr::new(table.base_address.address),
+        REGISTER_BLOCK_SIZE,
+    )
+    .map_err(HpetError::Mapping)?;
+    let mut hpet = unsafe { Hpet::new(base) };
+    hpet.enable();
+
+    COUNTER_ADDRESS.store(hpet.counter_address().as_u64(), Ordering::SeqCst);
+    PERIOD_FS.store(hpet.period_fs(), Ordering::SeqCst);
+    if !hpet.has_64bit_counter() {
+        COUNTER_MASK.store(u32::MAX as u64, Ordering::SeqCst);
+    }
+
+    ok!(
+        "HPET at {:?} with {} timers, {} bit counter at {} Hz",
+        base,
+        hpet.timer_count(),
+        if has_64bit_counter() { 64 } else { 32 },
+        frequency()
+    );
+
+    crate::interrupts::disable_interrupts_for(|| *HPET.lock() = Some(hpet));
+    Ok(())
+}
+
+/// Frequency of the main counter in Hz.
+pub fn frequency() -> u64 {
+    match PERIOD_FS.load(Ordering::SeqCst) {
+        0 => 0,
+        period => 1_000_000_000_000_000 / period,
+    }
+}
+
+pub fn has_64bit_counter() -> bool {
+    COUNTER_MASK.load(Ordering::SeqCst) == u64::MAX
+}
+
+/// Value of the main counter, if there is an HPET.
+pub fn counter() -> Option<u64> {
+    match COUNTER_ADDRESS.load(Ordering::SeqCst) {
+        0 => None,
+        address => Some(
+            unsafe { core::ptr::read_volatile(address as *const u64) }
+                & COUNTER_MASK.load(Ordering::SeqCst),
+        ),
+    }
+}
+
+/// Time since the main counter was started. Only available with a 64 bit
+/// counter, a 32 bit counter wraps too early to be used as clock.
+pub fn nanoseconds() -> Option<u64> {
+    if !has_64bit_counter() {
+        return None;
+    }
+    Some(ticks_to_nanoseconds(counter()?))
+}
+
+/// Time since the main counter had the given value. With a 32 bit counter
+/// this must be less than one full turn of the counter.
+pub fn elapsed(start: u64) -> Duration {
+    Duration::from_nanos(ticks_to_nanoseconds(ticks_since(start)))
+}
+
+/// Busy waits for the given time with the main counter. Long waits are split
+/// into chunks of half a turn, so a 32 bit counter can not wrap past the start.
+pub fn sleep(duration: Duration) {
+    let max_chunk = COUNTER_MASK.load(Ordering::SeqCst) / 2;
+    let mut remaining = duration_to_ticks(duration);
+    while remaining > 0 {
+        let start = match counter() {
+            Some(start) => start,
+            None => return,
+        };
+        let chunk = remaining.min(max_chunk);
+        while ticks_since(start) < chunk {
+            core::hint::spin_loop();
+        }
+        remaining -= chunk;
+    }
+}
+
+/// Uses timer 0 instead of the PIT to raise IRQ0 with the given frequency.
+pub fn start_tick(frequency: u32) -> Result<(), HpetError> {
+    let period = duration_to_ticks(Duration::from_nanos(
+        1_000_000_000 / frequency.max(1) as u64,
+    ));
+
+    crate::interrupts::disable_interrupts_for(|| {
+        let mut hpet = HPET.lock();
+        let hpet = hpet.as_mut().ok_or(HpetError::NotFound)?;
+        hpet.set_legacy_replacement(true)?;
+        hpet.set_periodic(TICK_TIMER, period)
+    })?;
+
+    irq::register_irq(irq::TIMER_IRQ, tick_handler).expect("Could not register HPET handler");
+    ok!(
+        "HPET timer {} raises IRQ0 with {} Hz",
+        TICK_TIMER,
+        frequency
+    );
+    Ok(())
+}
+
+/// Number of interrupts of the tick timer.
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::SeqCst)
+}
+
+/// Raises one interrupt on the routed IRQ of the timer after the given time.
+#[allow(dead_code)]
+pub fn set_one_shot(timer: u8, delay: Duration) -> Result<(), HpetError> {
+    with_hpet(|hpet| hpet.set_one_shot(timer, duration_to_ticks(delay)))
+}
+
+/// Raises an interrupt on the routed IRQ of the timer with the given period.
+#[allow(dead_code)]
+pub fn set_periodic(timer: u8, period: Duration) -> Result<(), HpetError> {
+    with_hpet(|hpet| hpet.set_periodic(timer, duration_to_ticks(period)))
+}
+
+/// Connects a timer to an I/O APIC input, which needs the APIC.
+#[allow(dead_code)]
+pub fn set_route(timer: u8, irq: u8) -> Result<(), HpetError> {
+    with_hpet(|hpet| hpet.set_route(timer, irq))
+}
+
+fn with_hpet<F>(func: F) -> Result<(), HpetError>
+where
+    F: FnOnce(&mut Hpet) -> Result<(), HpetError>,
+{
+    crate::interrupts::disable_interrupts_for(|| {
+        let mut hpet = HPET.lock();
+        func(hpet.as_mut().ok_or(HpetError::NotFound)?)
+    })
+}
+
+fn duration_to_ticks(duration: Duration) -> u64 {
+    let period = PERIOD_FS.load(Ordering::SeqCst).max(1) as u128;
+    (duration.as_nanos() * FEMTOSECONDS_PER_NANOSECOND as u128 / period) as u64
+}
+
+fn ticks_since(start: u64) -> u64 {
+    counter().unwrap_or(start).wrapping_sub(start) & COUNTER_MASK.load(Ordering::SeqCst)
+}
+
+fn ticks_to_nanoseconds(ticks: u64) -> u64 {
+    let period = PERIOD_FS.load(Ordering::SeqCst) as u128;
+    (ticks as u128 * period / FEMTOSECONDS_PER_NANOSECOND as u128) as u64
+}
+
+fn tick_handler(_stack_frame: &InterruptStackFrame) -> IrqReturn {
+    TICKS.fetch_add(1, Ordering::SeqCst);
+    IrqReturn::Handled
+}
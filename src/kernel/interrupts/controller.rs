@@ -0,0 +1,93 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::apic::{self, ApicTopology, SPURIOUS_VECTOR};
+use crate::pic::{PICS, PIC_1_OFFSET};
+
+use super::irq::LEGACY_IRQ_COUNT;
+
+static APIC_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Sets up the APIC if the processor has one and the 8259 PICs otherwise.
+///
+/// The PICs are always remapped, so that stray interrupts from them do not
+/// arrive on exception vectors.
+pub fn init(topology: ApicTopology) {
+    unsafe {
+        PICS.lock().init();
+    }
+
+    if !apic::is_supported() {
+        ok!("No APIC found, using the 8259 PICs");
+        return;
+    }
+
+    PICS.lock().disable_all();
+    match apic::init(topology) {
+        Ok(()) => APIC_ENABLED.store(true, Ordering::SeqCst),
+        Err(error) => {
+            serial_println!("APIC initialization failed: {:?}", error);
+            ok!("Falling back to the 8259 PICs");
+            unsafe {
+                PICS.lock().init();
+            }
+        }
+    }
+}
+
+pub fn uses_apic() -> bool {
+    APIC_ENABLED.load(Ordering::SeqCst)
+}
+
+/// Enables delivery of the legacy IRQ.
+pub fn unmask(irq: u8) {
+    if uses_apic() {
+        apic::unmask_irq(irq);
+    } else {
+        PICS.lock().unmask(irq);
+    }
+}
+
+pub fn mask(irq: u8) {
+    if uses_apic() {
+        apic::mask_irq(irq);
+    } else {
+        PICS.lock().mask(irq);
+    }
+}
+
+/// Returns true if the interrupt on the vector is spurious. It is already
+/// finished in this case and must not be handled.
+pub fn handle_spurious(vector: u8) -> bool {
+    if uses_apic() {
+        return vector == SPURIOUS_VECTOR;
+    }
+
+    match legacy_irq(vector) {
+        Some(irq) => {
+            let pics = PICS.lock();
+            if pics.is_spurious(irq) {
+                pics.end_spurious_interrupt(irq);
+                return true;
+            }
+            false
+        }
+        None => false,
+    }
+}
+
+pub fn end_of_interrupt(vector: u8) {
+    if uses_apic() {
+        apic::end_of_interrupt();
+    } else if let Some(irq) = legacy_irq(vector) {
+        PICS.lock().send_end_of_interrupt(irq);
+    }
+}
+
+/// The legacy interrupt line of a vector.
+pub fn legacy_irq(vector: u8) -> Option<u8> {
+    if (PIC_1_OFFSET..PIC_1_OFFSET + LEGACY_IRQ_COUNT).contains(&vector) {
+        Some(vector - PIC_1_OFFSET)
+    } else {
+        None
+    }
+}
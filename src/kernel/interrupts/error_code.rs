@@ -0,0 +1,121 @@
+use core::fmt;
+
+/// Error code which the CPU pushes on a page fault.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PageFaultErrorCode(u64);
+
+impl PageFaultErrorCode {
+    pub fn new(error_code: u64) -> Self {
+        PageFaultErrorCode(error_code)
+    }
+
+    fn bit(&self, bit: u8) -> bool {
+        self.0 & (1 << bit) != 0
+    }
+
+    /// The page was present, so the fault is a protection violation.
+    /// Otherwise the page was not present.
+    pub fn protection_violation(&self) -> bool {
+        self.bit(0)
+    }
+
+    /// The access was a write. Otherwise it was a read.
+    pub fn caused_by_write(&self) -> bool {
+        self.bit(1)
+    }
+
+    /// The access happened in user mode (CPL 3).
+    pub fn user_mode(&self) -> bool {
+        self.bit(2)
+    }
+
+    /// A reserved bit was set in one of the page table entries.
+    pub fn malformed_table(&self) -> bool {
+        self.bit(3)
+    }
+
+    pub fn instruction_fetch(&self) -> bool {
+        self.bit(4)
+    }
+
+    /// The access violated the protection key rights.
+    pub fn protection_key(&self) -> bool {
+        self.bit(5)
+    }
+
+    /// The access was a shadow stack access.
+    pub fn shadow_stack(&self) -> bool {
+        self.bit(6)
+    }
+}
+
+impl fmt::Debug for PageFaultErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PageFaultErrorCode")
+            .field("value", &format_args!("{:#x}", self.0))
+            .field("protection_violation", &self.protection_violation())
+            .field("caused_by_write", &self.caused_by_write())
+            .field("user_mode", &self.user_mode())
+            .field("malformed_table", &self.malformed_table())
+            .field("instruction_fetch", &self.instruction_fetch())
+            .field("protection_key", &self.protection_key())
+            .field("shadow_stack", &self.shadow_stack())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptorTable {
+    Gdt,
+    Idt,
+    Ldt,
+}
+
+/// Error code of exceptions which refer to a segment selector
+/// (invalid TSS, segment not present, stack segment and general protection fault).
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct SelectorErrorCode(u64);
+
+impl SelectorErrorCode {
+    pub fn new(error_code: u64) -> Self {
+        SelectorErrorCode(error_code)
+    }
+
+    /// The exception is not related to a segment selector.
+    pub fn is_null(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The exception originated externally to the processor.
+    pub fn external(&self) -> bool {
+        self.0 & 0x1 != 0
+    }
+
+    pub fn descriptor_table(&self) -> DescriptorTable {
+        match (self.0 >> 1) & 0b11 {
+            0b00 => DescriptorTable::Gdt,
+            0b10 => DescriptorTable::Ldt,
+            // 0b01 and 0b11 both refer to the IDT
+            _ => DescriptorTable::Idt,
+        }
+    }
+
+    pub fn index(&self) -> u16 {
+        ((self.0 >> 3) & 0x1fff) as u16
+    }
+}
+
+impl fmt::Debug for SelectorErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("SelectorErrorCode");
+        s.field("value", &format_args!("{:#x}", self.0));
+        if !self.is_null() {
+            s.field("external", &self.external());
+            s.field("descriptor_table", &self.descriptor_table());
+            s.field("index", &self.index());
+        }
+        s.finish()
+    }
+}
@@ -1,47 +1,183 @@
+use core::fmt;
+
+use super::error_code::{PageFaultErrorCode, SelectorErrorCode};
 use super::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
-use super::interrupt_descriptor_table::table::InterruptType;
-use crate::{asm::Port, pic::PICS};
+use super::page_fault;
+use crate::asm::control_registers::Cr2;
+use crate::memory::virt_addr::VirtAddr;
+
+/// Report which is printed for every CPU exception.
+struct ExceptionReport<'a> {
+    exception: &'a str,
+    details: Option<&'a dyn fmt::Debug>,
+    stack_frame: &'a InterruptStackFrame,
+}
 
-const KEYBOARD_CONTROLLER_ADDRESS: u16 = 0x60;
+impl fmt::Display for ExceptionReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "EXCEPTION: {}", self.exception)?;
+        if let Some(details) = self.details {
+            writeln!(f, "{:#?}", details)?;
+        }
+        write!(f, "{:#?}", self.stack_frame)
+    }
+}
 
-pub extern "x86-interrupt" fn divide_by_zero_handler(_stack_frame: &InterruptStackFrame) {
-    panic!("EXCEPTION: DIVIDE BY ZERO");
+/// Prints the report on VGA and serial and continues execution.
+fn report(exception: &str, details: Option<&dyn fmt::Debug>, stack_frame: &InterruptStackFrame) {
+    let report = ExceptionReport {
+        exception,
+        details,
+        stack_frame,
+    };
+    println!("{}", report);
+    serial_println!("{}", report);
+}
+
+/// Prints the report through the panic handler, which never returns.
+fn crash(
+    exception: &str,
+    details: Option<&dyn fmt::Debug>,
+    stack_frame: &InterruptStackFrame,
+) -> ! {
+    let report = ExceptionReport {
+        exception,
+        details,
+        stack_frame,
+    };
+    panic!("{}", report);
+}
+
+pub extern "x86-interrupt" fn divide_by_zero_handler(stack_frame: &InterruptStackFrame) {
+    crash("DIVIDE BY ZERO", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn debug_handler(stack_frame: &InterruptStackFrame) {
+    report("DEBUG", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn non_maskable_interrupt_handler(stack_frame: &InterruptStackFrame) {
+    crash("NON MASKABLE INTERRUPT", None, stack_frame);
 }
 
 pub extern "x86-interrupt" fn breakpoint_handler(stack_frame: &InterruptStackFrame) {
-    println!("EXCEPTION BREAKPOINT:\n {:#?}\n", stack_frame);
+    report("BREAKPOINT", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn overflow_handler(stack_frame: &InterruptStackFrame) {
+    crash("OVERFLOW", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn bound_range_exceeded_handler(stack_frame: &InterruptStackFrame) {
+    crash("BOUND RANGE EXCEEDED", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn invalid_opcode_handler(stack_frame: &InterruptStackFrame) {
+    crash("INVALID OPCODE", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn device_not_available_handler(stack_frame: &InterruptStackFrame) {
+    crash("DEVICE NOT AVAILABLE", None, stack_frame);
 }
 
 pub extern "x86-interrupt" fn double_fault_handler(
     stack_frame: &InterruptStackFrame,
     error_code: u64,
 ) -> ! {
-    panic!(
-        "EXCEPTION: DOUBLE FAULT (error_code: {})\n{:#?}",
-        error_code, stack_frame
-    );
+    // The error code of a double fault is always zero
+    crash("DOUBLE FAULT", Some(&error_code), stack_frame);
 }
 
-pub extern "x86-interrupt" fn page_fault_handler(
-    _stack_frame: &InterruptStackFrame,
-    _error_code: u64,
+pub extern "x86-interrupt" fn coprocessor_segment_overrun_handler(
+    stack_frame: &InterruptStackFrame,
+) {
+    crash("COPROCESSOR SEGMENT OVERRUN", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn invalid_tss_handler(
+    stack_frame: &InterruptStackFrame,
+    error_code: u64,
+) {
+    let error_code = SelectorErrorCode::new(error_code);
+    crash("INVALID TSS", Some(&error_code), stack_frame);
+}
+
+pub extern "x86-interrupt" fn segment_not_present_handler(
+    stack_frame: &InterruptStackFrame,
+    error_code: u64,
 ) {
-    panic!("PAGE FAULT");
+    let error_code = SelectorErrorCode::new(error_code);
+    crash("SEGMENT NOT PRESENT", Some(&error_code), stack_frame);
 }
 
-pub extern "x86-interrupt" fn timer_handler(_stack_frame: &InterruptStackFrame) {
-    PICS.lock().send_end_of_interrupt(InterruptType::Timer);
+pub extern "x86-interrupt" fn stack_segment_fault_handler(
+    stack_frame: &InterruptStackFrame,
+    error_code: u64,
+) {
+    let error_code = SelectorErrorCode::new(error_code);
+    crash("STACK SEGMENT FAULT", Some(&error_code), stack_frame);
 }
 
-pub extern "x86-interrupt" fn keyboard_handler(_stack_frame: &InterruptStackFrame) {
-    let port = Port::new(KEYBOARD_CONTROLLER_ADDRESS);
-    let scancode = unsafe { port.read() };
+pub extern "x86-interrupt" fn general_protection_fault_handler(
+    stack_frame: &InterruptStackFrame,
+    error_code: u64,
+) {
+    let error_code = SelectorErrorCode::new(error_code);
+    crash("GENERAL PROTECTION FAULT", Some(&error_code), stack_frame);
+}
 
-    // MSB bit indicates if key is pressed (0) or released (1)
-    if scancode & 0x80 == 0 {
-        serial_println!("Press: {} ", scancode);
-    } else {
-        serial_println!("Release: {}", scancode & 0x7f);
+pub extern "x86-interrupt" fn page_fault_handler(
+    stack_frame: &InterruptStackFrame,
+    error_code: u64,
+) {
+    #[derive(Debug)]
+    struct PageFault {
+        accessed_address: VirtAddr,
+        error_code: PageFaultErrorCode,
+    }
+
+    let page_fault = PageFault {
+        accessed_address: Cr2::read(),
+        error_code: PageFaultErrorCode::new(error_code),
+    };
+
+    if page_fault::try_resolve(
+        page_fault.accessed_address,
+        page_fault.error_code,
+        stack_frame,
+    ) {
+        return;
     }
-    PICS.lock().send_end_of_interrupt(InterruptType::Keyboard);
+
+    crash("PAGE FAULT", Some(&page_fault), stack_frame);
+}
+
+pub extern "x86-interrupt" fn x87_floating_point_handler(stack_frame: &InterruptStackFrame) {
+    crash("X87 FLOATING POINT", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn alignment_check_handler(
+    stack_frame: &InterruptStackFrame,
+    error_code: u64,
+) {
+    crash("ALIGNMENT CHECK", Some(&error_code), stack_frame);
+}
+
+pub extern "x86-interrupt" fn machine_check_handler(stack_frame: &InterruptStackFrame) -> ! {
+    crash("MACHINE CHECK", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn simd_floating_point_handler(stack_frame: &InterruptStackFrame) {
+    crash("SIMD FLOATING POINT", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn virtualization_handler(stack_frame: &InterruptStackFrame) {
+    crash("VIRTUALIZATION", None, stack_frame);
+}
+
+pub extern "x86-interrupt" fn security_exception_handler(
+    stack_frame: &InterruptStackFrame,
+    error_code: u64,
+) {
+    crash("SECURITY EXCEPTION", Some(&error_code), stack_frame);
 }
@@ -0,0 +1,205 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use super::controller;
+use super::interrupt_descriptor_table::entry::HandlerFunc;
+use super::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::pic::PIC_1_OFFSET;
+use crate::ylib::sync::mutex::Mutex;
+
+/// Vector of the first hardware interrupt, all vectors below are CPU exceptions.
+pub const FIRST_VECTOR: u8 = 32;
+pub const VECTOR_COUNT: usize = 256 - FIRST_VECTOR as usize;
+/// Number of interrupt lines of the two chained 8259 PICs.
+pub const LEGACY_IRQ_COUNT: u8 = 16;
+
+pub const TIMER_IRQ: u8 = 0;
+pub const KEYBOARD_IRQ: u8 = 1;
+pub const RTC_IRQ: u8 = 8;
+pub const SERIAL_IRQ: u8 = 4;
+pub const MOUSE_IRQ: u8 = 12;
+
+/// Maximum number of handlers which can share one vector.
+const MAX_SHARED_HANDLERS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IrqReturn {
+    /// The interrupt was raised by the device of the handler.
+    Handled,
+    /// The interrupt was not meant for this handler.
+    #[allow(dead_code)]
+    NotHandled,
+}
+
+pub type IrqHandler = fn(&InterruptStackFrame) -> IrqReturn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IrqError {
+    InvalidIrq(u8),
+    InvalidVector(u8),
+    /// All handler slots of the vector are taken.
+    VectorFull(u8),
+    #[allow(dead_code)]
+    HandlerNotRegistered(u8),
+}
+
+type HandlerSlots = [Option<IrqHandler>; MAX_SHARED_HANDLERS];
+
+static HANDLERS: Mutex<[HandlerSlots; VECTOR_COUNT]> =
+    Mutex::new([[None; MAX_SHARED_HANDLERS]; VECTOR_COUNT]);
+
+// Only used to initialize the array, every element is a new atomic
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicU64 = AtomicU64::new(0);
+/// Number of interrupts per vector, without the spurious ones.
+static COUNTS: [AtomicU64; VECTOR_COUNT] = [ZERO; VECTOR_COUNT];
+static SPURIOUS_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Adds a handler for one of the 16 legacy interrupt lines.
+/// The line is unmasked once the handler is in place.
+pub fn register_irq(irq: u8, handler: IrqHandler) -> Result<(), IrqError> {
+    register_vector(legacy_irq_vector(irq)?, handler)?;
+    super::disable_interrupts_for(|| controller::unmask(irq));
+    Ok(())
+}
+
+/// The line is masked again when its last handler is removed.
+pub fn unregister_irq(irq: u8, handler: IrqHandler) -> Result<(), IrqError> {
+    let vector = legacy_irq_vector(irq)?;
+    unregister_vector(vector, handler)?;
+    super::disable_interrupts_for(|| {
+        if HANDLERS.lock()[vector_index(vector).unwrap()]
+            .iter()
+            .all(|slot| slot.is_none())
+        {
+            controller::mask(irq);
+        }
+    });
+    Ok(())
+}
+
+/// Adds a handler for an interrupt vector (32 to 255). Handlers of the same
+/// vector are called in the order of their registration.
+pub fn register_vector(vector: u8, handler: IrqHandler) -> Result<(), IrqError> {
+    let index = vector_index(vector)?;
+
+    super::disable_interrupts_for(|| {
+        let mut handlers = HANDLERS.lock();
+        let slot = handlers[index]
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(IrqError::VectorFull(vector))?;
+        *slot = Some(handler);
+        Ok(())
+    })
+}
+
+#[allow(dead_code)]
+pub fn unregister_vector(vector: u8, handler: IrqHandler) -> Result<(), IrqError> {
+    let index = vector_index(vector)?;
+
+    super::disable_interrupts_for(|| {
+        let mut handlers = HANDLERS.lock();
+        let slot = handlers[index]
+            .iter_mut()
+            .find(
+                |slot| matches!(slot, Some(registered) if *registered as usize == handler as usize),
+            )
+            .ok_or(IrqError::HandlerNotRegistered(vector))?;
+        *slot = None;
+        Ok(())
+    })
+}
+
+/// Number of interrupts which arrived on the vector.
+pub fn count(vector: u8) -> u64 {
+    match vector_index(vector) {
+        Ok(index) => COUNTS[index].load(Ordering::Relaxed),
+        Err(_) => 0,
+    }
+}
+
+pub fn spurious_count() -> u64 {
+    SPURIOUS_COUNT.load(Ordering::Relaxed)
+}
+
+/// Number of handlers registered for the vector.
+pub fn handler_count(vector: u8) -> usize {
+    match vector_index(vector) {
+        Ok(index) => super::disable_interrupts_for(|| {
+            HANDLERS.lock()[index]
+                .iter()
+                .filter(|slot| slot.is_some())
+                .count()
+        }),
+        Err(_) => 0,
+    }
+}
+
+fn legacy_irq_vector(irq: u8) -> Result<u8, IrqError> {
+    if irq >= LEGACY_IRQ_COUNT {
+        return Err(IrqError::InvalidIrq(irq));
+    }
+    Ok(PIC_1_OFFSET + irq)
+}
+
+fn vector_index(vector: u8) -> Result<usize, IrqError> {
+    if vector < FIRST_VECTOR {
+        return Err(IrqError::InvalidVector(vector));
+    }
+    Ok((vector - FIRST_VECTOR) as usize)
+}
+
+fn dispatch(vector: u8, stack_frame: &InterruptStackFrame) {
+    if controller::handle_spurious(vector) {
+        SPURIOUS_COUNT.fetch_add(1, Ordering::Relaxed);
+        return;
+    }
+    COUNTS[(vector - FIRST_VECTOR) as usize].fetch_add(1, Ordering::Relaxed);
+
+    // Copy the handlers so they can (un)register handlers themselves
+    let handlers = HANDLERS.lock()[(vector - FIRST_VECTOR) as usize];
+
+    let mut handled = false;
+    for handler in handlers.iter().flatten() {
+        if handler(stack_frame) == IrqReturn::Handled {
+            handled = true;
+        }
+    }
+
+    if !handled {
+        serial_println!("Unhandled interrupt on vector {}", vector);
+    }
+
+    controller::end_of_interrupt(vector);
+}
+
+extern "x86-interrupt" fn interrupt_stub<const VECTOR: u8>(stack_frame: &InterruptStackFrame) {
+    dispatch(VECTOR, stack_frame);
+}
+
+macro_rules! interrupt_stubs {
+    ($($row:literal),*) => {
+        [$(
+            interrupt_stub::<{ $row * 16 }>,
+            interrupt_stub::<{ $row * 16 + 1 }>,
+            interrupt_stub::<{ $row * 16 + 2 }>,
+            interrupt_stub::<{ $row * 16 + 3 }>,
+            interrupt_stub::<{ $row * 16 + 4 }>,
+            interrupt_stub::<{ $row * 16 + 5 }>,
+            interrupt_stub::<{ $row * 16 + 6 }>,
+            interrupt_stub::<{ $row * 16 + 7 }>,
+            interrupt_stub::<{ $row * 16 + 8 }>,
+            interrupt_stub::<{ $row * 16 + 9 }>,
+            interrupt_stub::<{ $row * 16 + 10 }>,
+            interrupt_stub::<{ $row * 16 + 11 }>,
+            interrupt_stub::<{ $row * 16 + 12 }>,
+            interrupt_stub::<{ $row * 16 + 13 }>,
+            interrupt_stub::<{ $row * 16 + 14 }>,
+            interrupt_stub::<{ $row * 16 + 15 }>,
+        )*]
+    };
+}
+
+/// One entry point per vector (32 to 255), each calls `dispatch` with its vector.
+pub static INTERRUPT_STUBS: [HandlerFunc; VECTOR_COUNT] =
+    interrupt_stubs!(2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
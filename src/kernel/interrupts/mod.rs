@@ -1,15 +1,29 @@
+pub mod controller;
+pub mod error_code;
 pub mod interrupt_descriptor_table;
 pub mod interrupt_handler;
+pub mod irq;
+pub mod page_fault;
 
 use crate::ylib::sync::lazy::Lazy;
-use interrupt_descriptor_table::table::{InterruptDescriptorTable, InterruptType};
+use interrupt_descriptor_table::table::InterruptDescriptorTable;
 use interrupt_handler::*;
+use irq::INTERRUPT_STUBS;
 
 static IDT: Lazy<InterruptDescriptorTable, fn() -> InterruptDescriptorTable> = Lazy::new(|| {
     let mut idt = InterruptDescriptorTable::new();
 
     idt.divide_by_zero_error.set_handler(divide_by_zero_handler);
+    idt.debug.set_handler(debug_handler);
+    idt.non_maskable_interrupt
+        .set_handler(non_maskable_interrupt_handler);
     idt.breakpoint.set_handler(breakpoint_handler);
+    idt.overflow.set_handler(overflow_handler);
+    idt.bound_range_exceeded
+        .set_handler(bound_range_exceeded_handler);
+    idt.invalid_opcode.set_handler(invalid_opcode_handler);
+    idt.device_not_available
+        .set_handler(device_not_available_handler);
 
     let double_fault_options = idt.double_fault.set_handler(double_fault_handler);
 
@@ -17,10 +31,29 @@ static IDT: Lazy<InterruptDescriptorTable, fn() -> InterruptDescriptorTable> = L
         double_fault_options.set_stack_index(0);
     }
 
+    idt.coprocessor_segment_overrun
+        .set_handler(coprocessor_segment_overrun_handler);
+    idt.invalid_tss.set_handler(invalid_tss_handler);
+    idt.segment_not_present
+        .set_handler(segment_not_present_handler);
+    idt.stack_segment_fault
+        .set_handler(stack_segment_fault_handler);
+    idt.general_protection_fault
+        .set_handler(general_protection_fault_handler);
     idt.page_fault.set_handler(page_fault_handler);
+    idt.x87_floating_point
+        .set_handler(x87_floating_point_handler);
+    idt.alignment_check.set_handler(alignment_check_handler);
+    idt.machine_check.set_handler(machine_check_handler);
+    idt.simd_floating_point
+        .set_handler(simd_floating_point_handler);
+    idt.virtualization.set_handler(virtualization_handler);
+    idt.security_exception
+        .set_handler(security_exception_handler);
 
-    idt[InterruptType::Timer].set_handler(timer_handler);
-    idt[InterruptType::Keyboard].set_handler(keyboard_handler);
+    for (entry, stub) in idt.interrupts.iter_mut().zip(INTERRUPT_STUBS.iter()) {
+        entry.set_handler(*stub);
+    }
 
     idt
 });
@@ -33,7 +66,7 @@ pub fn init_idt() {
 
 pub fn disable_interrupts_for<F, R>(func: F) -> R
 where
-    F: Fn() -> R,
+    F: FnOnce() -> R,
 {
     let interrupts_enabled = crate::asm::flags::are_interrupts_enabled();
 
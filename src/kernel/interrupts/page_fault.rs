@@ -0,0 +1,35 @@
+use super::error_code::PageFaultErrorCode;
+use super::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::memory::virt_addr::VirtAddr;
+use crate::ylib::sync::mutex::Mutex;
+
+/// Tries to resolve a page fault (e.g. by demand paging or copy-on-write).
+/// Returns true if the faulting access can be retried.
+pub type PageFaultHandler = fn(VirtAddr, PageFaultErrorCode, &InterruptStackFrame) -> bool;
+
+static PAGE_FAULT_HANDLER: Mutex<Option<PageFaultHandler>> = Mutex::new(None);
+
+/// Routes all page faults to the given handler. Replaces the previous handler.
+#[allow(dead_code)]
+pub fn set_handler(handler: PageFaultHandler) {
+    super::disable_interrupts_for(|| *PAGE_FAULT_HANDLER.lock() = Some(handler));
+}
+
+#[allow(dead_code)]
+pub fn remove_handler() {
+    super::disable_interrupts_for(|| *PAGE_FAULT_HANDLER.lock() = None);
+}
+
+/// Returns true if the registered handler resolved the page fault.
+pub fn try_resolve(
+    address: VirtAddr,
+    error_code: PageFaultErrorCode,
+    stack_frame: &InterruptStackFrame,
+) -> bool {
+    // Do not hold the lock while the handler runs, it may fault itself
+    let handler = *PAGE_FAULT_HANDLER.lock();
+    match handler {
+        Some(handler) => handler(address, error_code, stack_frame),
+        None => false,
+    }
+}
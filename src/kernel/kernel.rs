@@ -1,6 +1,9 @@
 #![no_std] // don't link the Rust standard library
 #![feature(asm)]
 #![feature(abi_x86_interrupt)]
+#![feature(alloc_error_handler)]
+
+extern crate alloc;
 
 #[macro_use]
 pub mod vga_buffer;
@@ -8,42 +11,125 @@ pub mod vga_buffer;
 #[macro_use]
 mod serial;
 
+mod acpi;
+mod apic;
 mod asm;
+mod clock;
+#[macro_use]
+mod console;
+mod hpet;
 
 #[path = "../ylib/mod.rs"]
 mod ylib;
 
 mod interrupts;
+mod keyboard;
 mod memory;
+mod mouse;
+mod multiboot;
 mod pic;
+mod pit;
+mod power;
+mod ps2;
+mod rtc;
+mod shell;
+mod timer;
 
+use core::alloc::Layout;
 use core::panic::PanicInfo;
+use multiboot::BootInformation;
 
 #[no_mangle]
-pub extern "C" fn _start() -> ! {
+pub extern "C" fn _start(multiboot_information_address: usize) -> ! {
     clear_screen!();
     println!("Starting YaOS Kernel");
     serial_println!("Starting YaOS Kernel");
 
-    init();
+    let boot_information = unsafe { BootInformation::load(multiboot_information_address) }
+        .expect("Invalid multiboot information structure");
 
-    ok!("Booting finished");
+    init(&boot_information);
 
-    asm::halt::halt_loop();
+    ok!(
+        "Booting finished after {:?} ({} ticks)",
+        clock::uptime(),
+        clock::ticks()
+    );
+
+    shell::run();
 }
 
 /// This function is called on panic.
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
     println!("{}", info);
+    serial_println!("{}", info);
+
+    match power::panic_action() {
+        power::PanicAction::Halt => keyboard::scrollback_loop(),
+        power::PanicAction::Reboot => power::reboot(),
+        power::PanicAction::Shutdown => {
+            let error = power::shutdown();
+            serial_println!("Shutdown failed: {:?}", error);
+        }
+    }
     asm::halt::halt_loop();
 }
 
-fn init() {
+#[alloc_error_handler]
+fn alloc_error_handler(layout: Layout) -> ! {
+    serial_println!("ALLOCATION ERROR: {:?}", layout);
+    panic!("Allocation error: {:?}", layout);
+}
+
+fn init(boot_information: &BootInformation) {
+    ok!(
+        "Load multiboot information at {:#x}",
+        boot_information.start_address()
+    );
+
+    multiboot::set_boot_information(*boot_information);
+    let command_line = boot_information
+        .command_line_tag()
+        .and_then(|tag| tag.command_line().ok());
+
+    memory::frame_allocator::init(boot_information);
+    memory::paging::init();
+    memory::heap::init().expect("Heap initialization failed");
     memory::global_descriptor_table::init();
     interrupts::init_idt();
+    if let Err(error) = acpi::init(boot_information) {
+        serial_println!("ACPI initialization failed: {:?}", error);
+    }
+    let apic_topology = acpi::tables()
+        .and_then(|tables| tables.madt)
+        .map(|madt| madt.apic_topology())
+        .unwrap_or_default();
+    interrupts::controller::init(apic_topology);
+    power::init(command_line);
+    if let Err(error) = hpet::init() {
+        serial_println!("HPET initialization failed: {:?}", error);
+    }
+    clock::start_ticks(
+        clock::tick_source_from_command_line(command_line),
+        pit::DEFAULT_FREQUENCY,
+    );
+    clock::init();
+    rtc::init();
+    timer::init();
+    serial::init_input();
+    if let Err(error) = ps2::init() {
+        serial_println!("PS/2 controller initialization failed: {:?}", error);
+    }
+    if ps2::has_keyboard() {
+        keyboard::init(command_line);
+    }
+    if ps2::has_mouse() {
+        if let Err(error) = mouse::init() {
+            serial_println!("Mouse initialization failed: {:?}", error);
+        }
+    }
     unsafe {
-        pic::PICS.lock().init();
         asm::interrupts::enable_interrupts();
     }
 }
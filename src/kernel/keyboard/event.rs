@@ -0,0 +1,74 @@
+use super::key_code::KeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifiers {
+    pub left_shift: bool,
+    pub right_shift: bool,
+    pub left_ctrl: bool,
+    pub right_ctrl: bool,
+    pub alt: bool,
+    pub alt_gr: bool,
+    pub caps_lock: bool,
+    pub num_lock: bool,
+    pub scroll_lock: bool,
+}
+
+impl Modifiers {
+    pub const fn new() -> Self {
+        Modifiers {
+            left_shift: false,
+            right_shift: false,
+            left_ctrl: false,
+            right_ctrl: false,
+            alt: false,
+            alt_gr: false,
+            caps_lock: false,
+            num_lock: false,
+            scroll_lock: false,
+        }
+    }
+
+    pub fn shift(&self) -> bool {
+        self.left_shift || self.right_shift
+    }
+
+    pub fn ctrl(&self) -> bool {
+        self.left_ctrl || self.right_ctrl
+    }
+
+    /// Updates the state for a key event. Returns true if a lock key toggled.
+    pub fn update(&mut self, key: KeyCode, pressed: bool) -> bool {
+        match key {
+            KeyCode::LeftShift => self.left_shift = pressed,
+            KeyCode::RightShift => self.right_shift = pressed,
+            KeyCode::LeftCtrl => self.left_ctrl = pressed,
+            KeyCode::RightCtrl => self.right_ctrl = pressed,
+            KeyCode::LeftAlt => self.alt = pressed,
+            KeyCode::RightAlt => self.alt_gr = pressed,
+            KeyCode::CapsLock if pressed => {
+                self.caps_lock = !self.caps_lock;
+                return true;
+            }
+            KeyCode::NumLock if pressed => {
+                self.num_lock = !self.num_lock;
+                return true;
+            }
+            KeyCode::ScrollLock if pressed => {
+                self.scroll_lock = !self.scroll_lock;
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub key: KeyCode,
+    pub pressed: bool,
+    /// Modifier state after this event.
+    pub modifiers: Modifiers,
+    /// The character of the key in the active layout, if it has one.
+    pub char: Option<char>,
+}
@@ -0,0 +1,111 @@
+/// A physical key, named after its label on a US keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    Escape,
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Key5,
+    Key6,
+    Key7,
+    Key8,
+    Key9,
+    Key0,
+    Minus,
+    Equals,
+    Backspace,
+    Tab,
+    Q,
+    W,
+    E,
+    R,
+    T,
+    Y,
+    U,
+    I,
+    O,
+    P,
+    LeftBracket,
+    RightBracket,
+    Enter,
+    LeftCtrl,
+    A,
+    S,
+    D,
+    F,
+    G,
+    H,
+    J,
+    K,
+    L,
+    Semicolon,
+    Quote,
+    Backtick,
+    LeftShift,
+    Backslash,
+    Z,
+    X,
+    C,
+    V,
+    B,
+    N,
+    M,
+    Comma,
+    Period,
+    Slash,
+    RightShift,
+    KeypadMultiply,
+    LeftAlt,
+    Space,
+    CapsLock,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    NumLock,
+    ScrollLock,
+    Keypad0,
+    Keypad1,
+    Keypad2,
+    Keypad3,
+    Keypad4,
+    Keypad5,
+    Keypad6,
+    Keypad7,
+    Keypad8,
+    Keypad9,
+    KeypadMinus,
+    KeypadPlus,
+    KeypadPeriod,
+    KeypadEnter,
+    KeypadDivide,
+    /// The additional key next to the left shift on ISO keyboards (`<>` on German ones).
+    Iso102,
+    RightCtrl,
+    /// AltGr on most non-US layouts.
+    RightAlt,
+    Home,
+    Up,
+    PageUp,
+    Left,
+    Right,
+    End,
+    Down,
+    PageDown,
+    Insert,
+    Delete,
+    LeftGui,
+    RightGui,
+    Menu,
+    PrintScreen,
+    Pause,
+}
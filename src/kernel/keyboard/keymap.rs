@@ -0,0 +1,207 @@
+use super::event::Modifiers;
+use super::key_code::KeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Us,
+    German,
+}
+
+impl Layout {
+    pub fn from_name(name: &str) -> Option<Layout> {
+        match name {
+            "us" => Some(Layout::Us),
+            "de" => Some(Layout::German),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Us => "us",
+            Layout::German => "de",
+        }
+    }
+
+    /// Returns the character the key produces with the given modifiers.
+    pub fn translate(&self, key: KeyCode, modifiers: &Modifiers) -> Option<char> {
+        if let Some(char) = self.translate_keypad(key, modifiers) {
+            return Some(char);
+        }
+
+        let (normal, shifted, alt_gr) = match self {
+            Layout::Us => us(key)?,
+            Layout::German => german(key)?,
+        };
+
+        if modifiers.alt_gr && *self != Layout::Us {
+            return alt_gr;
+        }
+
+        // Caps lock only affects letters
+        let shift = if shifted.is_uppercase() {
+            modifiers.shift() != modifiers.caps_lock
+        } else {
+            modifiers.shift()
+        };
+        Some(if shift { shifted } else { normal })
+    }
+
+    fn translate_keypad(&self, key: KeyCode, modifiers: &Modifiers) -> Option<char> {
+        let digits = modifiers.num_lock && !modifiers.shift();
+        let decimal_separator = match self {
+            Layout::Us => '.',
+            Layout::German => ',',
+        };
+
+        match key {
+            KeyCode::KeypadDivide => Some('/'),
+            KeyCode::KeypadMultiply => Some('*'),
+            KeyCode::KeypadMinus => Some('-'),
+            KeyCode::KeypadPlus => Some('+'),
+            KeyCode::KeypadEnter => Some('\n'),
+            KeyCode::KeypadPeriod if digits => Some(decimal_separator),
+            KeyCode::Keypad0 if digits => Some('0'),
+            KeyCode::Keypad1 if digits => Some('1'),
+            KeyCode::Keypad2 if digits => Some('2'),
+            KeyCode::Keypad3 if digits => Some('3'),
+            KeyCode::Keypad4 if digits => Some('4'),
+            KeyCode::Keypad5 if digits => Some('5'),
+            KeyCode::Keypad6 if digits => Some('6'),
+            KeyCode::Keypad7 if digits => Some('7'),
+            KeyCode::Keypad8 if digits => Some('8'),
+            KeyCode::Keypad9 if digits => Some('9'),
+            _ => None,
+        }
+    }
+}
+
+/// Characters of the keys which are the same on all layouts.
+fn common(key: KeyCode) -> Option<(char, char, Option<char>)> {
+    match key {
+        KeyCode::Space => Some((' ', ' ', Some(' '))),
+        KeyCode::Tab => Some(('\t', '\t', None)),
+        KeyCode::Enter => Some(('\n', '\n', None)),
+        KeyCode::Backspace => Some(('\u{8}', '\u{8}', None)),
+        KeyCode::Escape => Some(('\u{1b}', '\u{1b}', None)),
+        _ => None,
+    }
+}
+
+fn letter(key: KeyCode) -> Option<char> {
+    use KeyCode::*;
+
+    let letter = match key {
+        A => 'a',
+        B => 'b',
+        C => 'c',
+        D => 'd',
+        E => 'e',
+        F => 'f',
+        G => 'g',
+        H => 'h',
+        I => 'i',
+        J => 'j',
+        K => 'k',
+        L => 'l',
+        M => 'm',
+        N => 'n',
+        O => 'o',
+        P => 'p',
+        Q => 'q',
+        R => 'r',
+        S => 's',
+        T => 't',
+        U => 'u',
+        V => 'v',
+        W => 'w',
+        X => 'x',
+        Y => 'y',
+        Z => 'z',
+        _ => return None,
+    };
+    Some(letter)
+}
+
+/// (normal, shift, AltGr) of the US layout.
+fn us(key: KeyCode) -> Option<(char, char, Option<char>)> {
+    use KeyCode::*;
+
+    if let Some(letter) = letter(key) {
+        return Some((letter, letter.to_ascii_uppercase(), None));
+    }
+
+    let (normal, shifted) = match key {
+        Backtick => ('`', '~'),
+        Key1 => ('1', '!'),
+        Key2 => ('2', '@'),
+        Key3 => ('3', '#'),
+        Key4 => ('4', '$'),
+        Key5 => ('5', '%'),
+        Key6 => ('6', '^'),
+        Key7 => ('7', '&'),
+        Key8 => ('8', '*'),
+        Key9 => ('9', '('),
+        Key0 => ('0', ')'),
+        Minus => ('-', '_'),
+        Equals => ('=', '+'),
+        LeftBracket => ('[', '{'),
+        RightBracket => (']', '}'),
+        Backslash => ('\\', '|'),
+        Semicolon => (';', ':'),
+        Quote => ('\'', '"'),
+        Comma => (',', '<'),
+        Period => ('.', '>'),
+        Slash => ('/', '?'),
+        Iso102 => ('\\', '|'),
+        key => return common(key),
+    };
+    Some((normal, shifted, None))
+}
+
+/// (normal, shift, AltGr) of the German QWERTZ layout. Dead keys produce
+/// their character directly.
+fn german(key: KeyCode) -> Option<(char, char, Option<char>)> {
+    use KeyCode::*;
+
+    match key {
+        // Y and Z are swapped
+        Y => return Some(('z', 'Z', None)),
+        Z => return Some(('y', 'Y', None)),
+        Q => return Some(('q', 'Q', Some('@'))),
+        E => return Some(('e', 'E', Some('€'))),
+        M => return Some(('m', 'M', Some('µ'))),
+        key => {
+            if let Some(letter) = letter(key) {
+                return Some((letter, letter.to_ascii_uppercase(), None));
+            }
+        }
+    }
+
+    let keys = match key {
+        Backtick => ('^', '°', None),
+        Key1 => ('1', '!', None),
+        Key2 => ('2', '"', Some('²')),
+        Key3 => ('3', '§', Some('³')),
+        Key4 => ('4', '$', None),
+        Key5 => ('5', '%', None),
+        Key6 => ('6', '&', None),
+        Key7 => ('7', '/', Some('{')),
+        Key8 => ('8', '(', Some('[')),
+        Key9 => ('9', ')', Some(']')),
+        Key0 => ('0', '=', Some('}')),
+        Minus => ('ß', '?', Some('\\')),
+        Equals => ('´', '`', None),
+        LeftBracket => ('ü', 'Ü', None),
+        RightBracket => ('+', '*', Some('~')),
+        Semicolon => ('ö', 'Ö', None),
+        Quote => ('ä', 'Ä', None),
+        Backslash => ('#', '\'', None),
+        Iso102 => ('<', '>', Some('|')),
+        Comma => (',', ';', None),
+        Period => ('.', ':', None),
+        Slash => ('-', '_', None),
+        key => return common(key),
+    };
+    Some(keys)
+}
@@ -0,0 +1,148 @@
+use core::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use self::event::{KeyEvent, Modifiers};
+use self::key_code::KeyCode;
+use self::keymap::Layout;
+use self::scancode::ScancodeDecoder;
+use crate::interrupts::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::interrupts::irq::{self, IrqReturn};
+use crate::multiboot::command_line;
+use crate::ps2::{self, keyboard::Leds};
+use crate::ylib::sync::mutex::Mutex;
+use crate::ylib::sync::ring_buffer::RingBuffer;
+
+pub mod event;
+pub mod key_code;
+pub mod keymap;
+pub mod scancode;
+
+const EVENT_BUFFER_SIZE: usize = 128;
+
+struct KeyboardState {
+    decoder: ScancodeDecoder,
+    modifiers: Modifiers,
+}
+
+/// Only used by the interrupt handler.
+static STATE: Mutex<KeyboardState> = Mutex::new(KeyboardState {
+    decoder: ScancodeDecoder::new(),
+    modifiers: Modifiers::new(),
+});
+static LAYOUT: AtomicU8 = AtomicU8::new(Layout::Us as u8);
+static EVENTS: RingBuffer<KeyEvent, EVENT_BUFFER_SIZE> = RingBuffer::new();
+/// Events which were dropped because nobody read them.
+static DROPPED_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Reads the layout from the kernel command line (`keymap=us|de`) and starts
+/// decoding the scancodes of IRQ1.
+pub fn init(command_line: Option<&str>) {
+    if let Some(layout) = command_line
+        .and_then(|command_line| command_line::argument(command_line, "keymap"))
+        .and_then(Layout::from_name)
+    {
+        set_layout(layout);
+    }
+
+    irq::register_irq(irq::KEYBOARD_IRQ, keyboard_handler)
+        .expect("Could not register keyboard handler");
+    ok!("Keyboard initialized with layout {}", layout().name());
+}
+
+pub fn layout() -> Layout {
+    match LAYOUT.load(Ordering::SeqCst) {
+        1 => Layout::German,
+        _ => Layout::Us,
+    }
+}
+
+pub fn set_layout(layout: Layout) {
+    LAYOUT.store(layout as u8, Ordering::SeqCst);
+}
+
+pub fn has_event() -> bool {
+    !EVENTS.is_empty()
+}
+
+/// Takes the oldest key event which was not read yet.
+pub fn read_event() -> Option<KeyEvent> {
+    EVENTS.pop()
+}
+
+#[allow(dead_code)]
+pub fn dropped_events() -> u64 {
+    DROPPED_EVENTS.load(Ordering::SeqCst)
+}
+
+fn keyboard_handler(_stack_frame: &InterruptStackFrame) -> IrqReturn {
+    let scancode = match ps2::read_keyboard_byte() {
+        Some(scancode) => scancode,
+        None => return IrqReturn::Handled,
+    };
+
+    let mut state = STATE.lock();
+    if let Some((key, pressed)) = state.decoder.add_byte(scancode) {
+        if pressed && state.modifiers.shift() && scroll_screen(key) {
+            return IrqReturn::Handled;
+        }
+        if state.modifiers.update(key, pressed) {
+            ps2::set_leds(Leds {
+                scroll_lock: state.modifiers.scroll_lock,
+                num_lock: state.modifiers.num_lock,
+                caps_lock: state.modifiers.caps_lock,
+            });
+        }
+
+        let event = KeyEvent {
+            key,
+            pressed,
+            modifiers: state.modifiers,
+            char: if pressed {
+                layout().translate(key, &state.modifiers)
+            } else {
+                None
+            },
+        };
+        if EVENTS.push(event).is_err() {
+            DROPPED_EVENTS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    IrqReturn::Handled
+}
+
+/// Shift+PageUp and Shift+PageDown scroll the screen. Returns true if the key
+/// was used for it.
+fn scroll_screen(key: KeyCode) -> bool {
+    match key {
+        KeyCode::PageUp => crate::vga_buffer::scroll_up(),
+        KeyCode::PageDown => crate::vga_buffer::scroll_down(),
+        _ => return false,
+    }
+    true
+}
+
+/// Keeps the screen scrollable after a panic. The keyboard is polled because
+/// interrupts cannot be trusted anymore.
+pub fn scrollback_loop() -> ! {
+    crate::asm::interrupts::disable_interrupts();
+    if !ps2::has_keyboard() {
+        crate::asm::halt::halt_loop();
+    }
+
+    let mut decoder = ScancodeDecoder::new();
+    let mut modifiers = Modifiers::new();
+    loop {
+        let scancode = match ps2::CONTROLLER.poll_first_port() {
+            Some(scancode) => scancode,
+            None => {
+                core::hint::spin_loop();
+                continue;
+            }
+        };
+        if let Some((key, pressed)) = decoder.add_byte(scancode) {
+            if pressed && modifiers.shift() && scroll_screen(key) {
+                continue;
+            }
+            modifiers.update(key, pressed);
+        }
+    }
+}
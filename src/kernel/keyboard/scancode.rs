@@ -0,0 +1,109 @@
+use super::key_code::KeyCode;
+
+const EXTENDED: u8 = 0xe0;
+/// Prefix of the Pause key, which only sends a make sequence: E1 1D 45 E1 9D C5.
+const PAUSE: u8 = 0xe1;
+const PAUSE_SEQUENCE_LENGTH: u8 = 6;
+const RELEASED: u8 = 0x80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    Extended,
+    /// Number of bytes of the pause sequence received so far.
+    Pause(u8),
+}
+
+/// Decodes scancode set 1 byte by byte into key presses and releases.
+pub struct ScancodeDecoder {
+    state: State,
+}
+
+impl ScancodeDecoder {
+    pub const fn new() -> Self {
+        ScancodeDecoder {
+            state: State::Start,
+        }
+    }
+
+    /// Returns the key and whether it was pressed once a sequence is complete.
+    pub fn add_byte(&mut self, byte: u8) -> Option<(KeyCode, bool)> {
+        match self.state {
+            State::Start => match byte {
+                EXTENDED => {
+                    self.state = State::Extended;
+                    None
+                }
+                PAUSE => {
+                    self.state = State::Pause(1);
+                    None
+                }
+                byte => key_code(byte & !RELEASED).map(|key| (key, byte & RELEASED == 0)),
+            },
+            State::Extended => {
+                self.state = State::Start;
+                extended_key_code(byte & !RELEASED).map(|key| (key, byte & RELEASED == 0))
+            }
+            State::Pause(received) => {
+                if received + 1 < PAUSE_SEQUENCE_LENGTH {
+                    self.state = State::Pause(received + 1);
+                    None
+                } else {
+                    self.state = State::Start;
+                    Some((KeyCode::Pause, true))
+                }
+            }
+        }
+    }
+}
+
+fn key_code(code: u8) -> Option<KeyCode> {
+    use KeyCode::*;
+
+    #[rustfmt::skip]
+    const KEYS: [Option<KeyCode>; 0x59] = [
+        None, Some(Escape), Some(Key1), Some(Key2), Some(Key3), Some(Key4), Some(Key5),
+        Some(Key6), Some(Key7), Some(Key8), Some(Key9), Some(Key0), Some(Minus), Some(Equals),
+        Some(Backspace), Some(Tab), Some(Q), Some(W), Some(E), Some(R), Some(T), Some(Y),
+        Some(U), Some(I), Some(O), Some(P), Some(LeftBracket), Some(RightBracket), Some(Enter),
+        Some(LeftCtrl), Some(A), Some(S), Some(D), Some(F), Some(G), Some(H), Some(J), Some(K),
+        Some(L), Some(Semicolon), Some(Quote), Some(Backtick), Some(LeftShift), Some(Backslash),
+        Some(Z), Some(X), Some(C), Some(V), Some(B), Some(N), Some(M), Some(Comma), Some(Period),
+        Some(Slash), Some(RightShift), Some(KeypadMultiply), Some(LeftAlt), Some(Space),
+        Some(CapsLock), Some(F1), Some(F2), Some(F3), Some(F4), Some(F5), Some(F6), Some(F7),
+        Some(F8), Some(F9), Some(F10), Some(NumLock), Some(ScrollLock), Some(Keypad7),
+        Some(Keypad8), Some(Keypad9), Some(KeypadMinus), Some(Keypad4), Some(Keypad5),
+        Some(Keypad6), Some(KeypadPlus), Some(Keypad1), Some(Keypad2), Some(Keypad3),
+        Some(Keypad0), Some(KeypadPeriod), None, None, Some(Iso102), Some(F11), Some(F12),
+    ];
+
+    KEYS.get(code as usize).copied().flatten()
+}
+
+/// Keys sent with the 0xE0 prefix. The fake shifts some keyboards send
+/// around them (0x2A, 0x36) are ignored.
+fn extended_key_code(code: u8) -> Option<KeyCode> {
+    use KeyCode::*;
+
+    match code {
+        0x1c => Some(KeypadEnter),
+        0x1d => Some(RightCtrl),
+        0x35 => Some(KeypadDivide),
+        0x37 => Some(PrintScreen),
+        0x38 => Some(RightAlt),
+        0x47 => Some(Home),
+        0x48 => Some(Up),
+        0x49 => Some(PageUp),
+        0x4b => Some(Left),
+        0x4d => Some(Right),
+        0x4f => Some(End),
+        0x50 => Some(Down),
+        0x51 => Some(PageDown),
+        0x52 => Some(Insert),
+        0x53 => Some(Delete),
+        0x5b => Some(LeftGui),
+        0x5c => Some(RightGui),
+        0x5d => Some(Menu),
+        _ => None,
+    }
+}
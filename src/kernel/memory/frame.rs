@@ -0,0 +1,57 @@
+use core::marker::PhantomData;
+
+use super::paging::page::{PageSize, Size4KiB};
+use super::phys_addr::PhysAddr;
+
+pub const FRAME_SIZE: u64 = Size4KiB::SIZE;
+
+/// A physical memory frame, 4 KiB by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Frame<S: PageSize = Size4KiB> {
+    number: u64,
+    size: PhantomData<S>,
+}
+
+impl<S: PageSize> Frame<S> {
+    pub fn containing_address(address: PhysAddr) -> Frame<S> {
+        Frame::from_number(address.as_u64() / S::SIZE)
+    }
+
+    pub fn from_number(number: u64) -> Frame<S> {
+        Frame {
+            number,
+            size: PhantomData,
+        }
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn start_address(&self) -> PhysAddr {
+        PhysAddr::new(self.number * S::SIZE)
+    }
+
+    pub fn range_inclusive(start: Frame<S>, end: Frame<S>) -> FrameIter<S> {
+        FrameIter { start, end }
+    }
+}
+
+pub struct FrameIter<S: PageSize> {
+    start: Frame<S>,
+    end: Frame<S>,
+}
+
+impl<S: PageSize> Iterator for FrameIter<S> {
+    type Item = Frame<S>;
+
+    fn next(&mut self) -> Option<Frame<S>> {
+        if self.start <= self.end {
+            let frame = self.start;
+            self.start.number += 1;
+            Some(frame)
+        } else {
+            None
+        }
+    }
+}
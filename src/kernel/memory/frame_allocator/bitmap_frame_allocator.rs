@@ -0,0 +1,191 @@
+use super::FrameAllocator;
+use crate::memory::frame::{Frame, FRAME_SIZE};
+use crate::memory::phys_addr::PhysAddr;
+use crate::multiboot::BootInformation;
+
+/// Physical memory above this address is ignored by the allocator.
+const MAX_PHYSICAL_MEMORY: u64 = 4 * 1024 * 1024 * 1024;
+const MAX_FRAMES: usize = (MAX_PHYSICAL_MEMORY / FRAME_SIZE) as usize;
+const BITMAP_ENTRIES: usize = MAX_FRAMES / 64;
+
+/// The first MiB contains the BIOS data area, the VGA buffer and other legacy
+/// structures, so it is never handed out.
+const LOW_MEMORY_END: u64 = 0x100000;
+
+/// Frame allocator which tracks every 4 KiB frame with a single bit.
+pub struct BitmapFrameAllocator {
+    /// A set bit marks a free frame.
+    bitmap: [u64; BITMAP_ENTRIES],
+    /// A set bit marks a frame which was free after `init`. Only these frames
+    /// can be deallocated.
+    usable: [u64; BITMAP_ENTRIES],
+    /// Index into the bitmap where the search for a free frame starts.
+    next_search_index: usize,
+    free_frames: usize,
+    total_frames: usize,
+}
+
+impl BitmapFrameAllocator {
+    pub const fn new() -> Self {
+        BitmapFrameAllocator {
+            bitmap: [0; BITMAP_ENTRIES],
+            usable: [0; BITMAP_ENTRIES],
+            next_search_index: 0,
+            free_frames: 0,
+            total_frames: 0,
+        }
+    }
+
+    /// Marks all available frames of the memory map as free, except the ones
+    /// used by the kernel image, the multiboot information and the modules.
+    pub fn init(&mut self, boot_information: &BootInformation) {
+        let memory_map_tag = boot_information
+            .memory_map_tag()
+            .expect("Memory map tag required");
+
+        for area in memory_map_tag.available_areas() {
+            let start = PhysAddr::new(area.start_address()).align_up(FRAME_SIZE);
+            let end = PhysAddr::new(area.end_address()).align_down(FRAME_SIZE);
+            if start >= end {
+                continue;
+            }
+            let first = Frame::containing_address(start);
+            let last = Frame::containing_address(PhysAddr::new(end.as_u64() - 1));
+            for frame in Frame::range_inclusive(first, last) {
+                self.mark_free(frame);
+            }
+        }
+
+        self.reserve(PhysAddr::zero(), PhysAddr::new(LOW_MEMORY_END));
+
+        let elf_sections_tag = boot_information
+            .elf_sections_tag()
+            .expect("Elf sections tag required");
+        let kernel_start = elf_sections_tag
+            .sections()
+            .filter(|section| section.is_allocated())
+            .map(|section| section.start_address())
+            .min()
+            .expect("Kernel has no allocated sections");
+        let kernel_end = elf_sections_tag
+            .sections()
+            .filter(|section| section.is_allocated())
+            .map(|section| section.end_address())
+            .max()
+            .unwrap();
+        self.reserve(PhysAddr::new(kernel_start), PhysAddr::new(kernel_end));
+
+        self.reserve(
+            PhysAddr::new(boot_information.start_address() as u64),
+            PhysAddr::new(boot_information.end_address() as u64),
+        );
+
+        for module in boot_information.module_tags() {
+            self.reserve(
+                PhysAddr::new(module.start_address()),
+                PhysAddr::new(module.end_address()),
+            );
+        }
+
+        self.usable.copy_from_slice(&self.bitmap);
+        self.total_frames = self.free_frames;
+    }
+
+    /// Number of frames which can currently be allocated.
+    pub fn free_frames(&self) -> usize {
+        self.free_frames
+    }
+
+    /// Number of frames which are currently allocated.
+    #[allow(dead_code)]
+    pub fn used_frames(&self) -> usize {
+        self.total_frames - self.free_frames
+    }
+
+    /// Number of frames managed by the allocator.
+    #[allow(dead_code)]
+    pub fn total_frames(&self) -> usize {
+        self.total_frames
+    }
+
+    /// Removes all frames which overlap with `start..end` from the free frames.
+    fn reserve(&mut self, start: PhysAddr, end: PhysAddr) {
+        if start >= end {
+            return;
+        }
+        let first = Frame::containing_address(start);
+        let last = Frame::containing_address(PhysAddr::new(end.as_u64() - 1));
+        for frame in Frame::range_inclusive(first, last) {
+            if self.is_free(frame) {
+                self.mark_used(frame);
+            }
+        }
+    }
+
+    fn position(frame: Frame) -> Option<(usize, u64)> {
+        let number = frame.number() as usize;
+        if number >= MAX_FRAMES {
+            return None;
+        }
+        Some((number / 64, 1 << (number % 64)))
+    }
+
+    fn is_free(&self, frame: Frame) -> bool {
+        match Self::position(frame) {
+            Some((index, mask)) => self.bitmap[index] & mask != 0,
+            None => false,
+        }
+    }
+
+    fn is_usable(&self, frame: Frame) -> bool {
+        match Self::position(frame) {
+            Some((index, mask)) => self.usable[index] & mask != 0,
+            None => false,
+        }
+    }
+
+    fn mark_free(&mut self, frame: Frame) {
+        if let Some((index, mask)) = Self::position(frame) {
+            if self.bitmap[index] & mask == 0 {
+                self.bitmap[index] |= mask;
+                self.free_frames += 1;
+            }
+        }
+    }
+
+    fn mark_used(&mut self, frame: Frame) {
+        if let Some((index, mask)) = Self::position(frame) {
+            self.bitmap[index] &= !mask;
+            self.free_frames -= 1;
+        }
+    }
+}
+
+impl FrameAllocator for BitmapFrameAllocator {
+    fn allocate_frame(&mut self) -> Option<Frame> {
+        if self.free_frames == 0 {
+            return None;
+        }
+
+        // Start at the last position and wrap around once
+        for offset in 0..BITMAP_ENTRIES {
+            let index = (self.next_search_index + offset) % BITMAP_ENTRIES;
+            let entry = self.bitmap[index];
+            if entry != 0 {
+                let bit = entry.trailing_zeros() as u64;
+                let frame = Frame::from_number(index as u64 * 64 + bit);
+                self.mark_used(frame);
+                self.next_search_index = index;
+                return Some(frame);
+            }
+        }
+        None
+    }
+
+    fn deallocate_frame(&mut self, frame: Frame) {
+        if !self.is_usable(frame) || self.is_free(frame) {
+            panic!("Invalid deallocation of {:?}", frame);
+        }
+        self.mark_free(frame);
+    }
+}
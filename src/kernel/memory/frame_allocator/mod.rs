@@ -0,0 +1,23 @@
+pub mod bitmap_frame_allocator;
+
+use self::bitmap_frame_allocator::BitmapFrameAllocator;
+use super::frame::Frame;
+use crate::multiboot::BootInformation;
+use crate::ylib::sync::mutex::Mutex;
+
+pub trait FrameAllocator {
+    fn allocate_frame(&mut self) -> Option<Frame>;
+    fn deallocate_frame(&mut self, frame: Frame);
+}
+
+pub static FRAME_ALLOCATOR: Mutex<BitmapFrameAllocator> = Mutex::new(BitmapFrameAllocator::new());
+
+pub fn init(boot_information: &BootInformation) {
+    let mut allocator = FRAME_ALLOCATOR.lock();
+    allocator.init(boot_information);
+    ok!(
+        "Frame allocator initialized with {} free frames ({} MiB)",
+        allocator.free_frames(),
+        allocator.free_frames() * 4 / 1024
+    );
+}
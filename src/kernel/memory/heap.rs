@@ -0,0 +1,94 @@
+use core::alloc::{GlobalAlloc, Layout};
+
+use super::frame_allocator::FRAME_ALLOCATOR;
+use super::paging::mapper::MapToError;
+use super::paging::{Page, PageTableFlags, ACTIVE_PAGE_TABLE};
+use super::virt_addr::VirtAddr;
+use crate::ylib::allocator::{HeapAllocator, HeapStatistics};
+use crate::ylib::sync::mutex::Mutex;
+
+#[cfg(feature = "bump-allocator")]
+use crate::ylib::allocator::bump::BumpAllocator as SelectedAllocator;
+#[cfg(feature = "fixed-size-block-allocator")]
+use crate::ylib::allocator::fixed_size_block::FixedSizeBlockAllocator as SelectedAllocator;
+#[cfg(feature = "linked-list-allocator")]
+use crate::ylib::allocator::linked_list::LinkedListAllocator as SelectedAllocator;
+
+#[cfg(not(any(
+    feature = "bump-allocator",
+    feature = "linked-list-allocator",
+    feature = "fixed-size-block-allocator"
+)))]
+compile_error!("No heap allocator selected, enable one of the *-allocator features.");
+
+#[cfg(any(
+    all(feature = "bump-allocator", feature = "linked-list-allocator"),
+    all(feature = "bump-allocator", feature = "fixed-size-block-allocator"),
+    all(
+        feature = "linked-list-allocator",
+        feature = "fixed-size-block-allocator"
+    )
+))]
+compile_error!(
+    "Multiple heap allocators selected, enable exactly one of the *-allocator features."
+);
+
+pub const HEAP_START: u64 = 0x4444_4444_0000;
+pub const HEAP_SIZE: u64 = 1024 * 1024;
+
+pub struct KernelAllocator<A: HeapAllocator> {
+    allocator: Mutex<A>,
+}
+
+unsafe impl<A: HeapAllocator> GlobalAlloc for KernelAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // An interrupt handler which allocates must not find the lock taken
+        crate::interrupts::disable_interrupts_for(|| self.allocator.lock().allocate(layout))
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        crate::interrupts::disable_interrupts_for(|| self.allocator.lock().deallocate(ptr, layout))
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: KernelAllocator<SelectedAllocator> = KernelAllocator {
+    allocator: Mutex::new(SelectedAllocator::new()),
+};
+
+/// Maps the heap region and initializes the global allocator.
+pub fn init() -> Result<(), MapToError> {
+    let heap_start = Page::containing_address(VirtAddr::new(HEAP_START));
+    let heap_end = Page::containing_address(VirtAddr::new(HEAP_START + HEAP_SIZE - 1));
+
+    {
+        let mut active_page_table = ACTIVE_PAGE_TABLE.lock();
+        let mut frame_allocator = FRAME_ALLOCATOR.lock();
+
+        for page in Page::range_inclusive(heap_start, heap_end) {
+            active_page_table.map(page, PageTableFlags::WRITABLE, &mut *frame_allocator)?;
+        }
+    }
+
+    unsafe {
+        ALLOCATOR
+            .allocator
+            .lock()
+            .init(HEAP_START as usize, HEAP_SIZE as usize);
+    }
+
+    ok!(
+        "Heap initialized at {:#x} with {} KiB ({})",
+        HEAP_START,
+        HEAP_SIZE / 1024,
+        core::any::type_name::<SelectedAllocator>()
+            .rsplit("::")
+            .next()
+            .unwrap()
+    );
+    Ok(())
+}
+
+pub fn statistics() -> HeapStatistics {
+    crate::interrupts::disable_interrupts_for(|| ALLOCATOR.allocator.lock().statistics())
+}
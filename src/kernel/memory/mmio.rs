@@ -0,0 +1,32 @@
+use super::frame::Frame;
+use super::frame_allocator::FRAME_ALLOCATOR;
+use super::paging::mapper::MapToError;
+use super::paging::{PageTableFlags, ACTIVE_PAGE_TABLE};
+use super::phys_addr::PhysAddr;
+use super::virt_addr::VirtAddr;
+
+/// Identity maps a region of memory mapped I/O as uncacheable and returns its
+/// virtual address. Pages which are already identity mapped, like the first
+/// GiB set up in boot.asm, are left as they are.
+pub fn identity_map(address: PhysAddr, size: u64) -> Result<VirtAddr, MapToError> {
+    let first: Frame = Frame::containing_address(address);
+    let last = Frame::containing_address(address + (size.max(1) - 1));
+    let flags = PageTableFlags::WRITABLE | PageTableFlags::NO_CACHE | PageTableFlags::WRITE_THROUGH;
+
+    crate::interrupts::disable_interrupts_for(|| {
+        let mut active_page_table = ACTIVE_PAGE_TABLE.lock();
+        let mut frame_allocator = FRAME_ALLOCATOR.lock();
+
+        for frame in Frame::range_inclusive(first, last) {
+            let start = frame.start_address();
+            match active_page_table.translate(VirtAddr::new(start.as_u64())) {
+                Some(mapped) if mapped == start => continue,
+                Some(_) => return Err(MapToError::PageAlreadyMapped),
+                None => active_page_table.identity_map(frame, flags, &mut *frame_allocator)?,
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(VirtAddr::new(address.as_u64()))
+}
@@ -1,4 +1,10 @@
+pub mod frame;
+pub mod frame_allocator;
 pub mod global_descriptor_table;
+pub mod heap;
+pub mod mmio;
+pub mod paging;
+pub mod phys_addr;
 pub mod privilege_level;
 pub mod segment_selector;
 pub mod task_state_segment;
@@ -0,0 +1,146 @@
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+
+use crate::memory::frame::Frame;
+use crate::memory::phys_addr::PhysAddr;
+
+/// Bits 12 to 51 of an entry contain the physical address.
+const ADDRESS_MASK: u64 = 0x000f_ffff_ffff_f000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PageTableFlags(u64);
+
+#[allow(dead_code)]
+impl PageTableFlags {
+    pub const PRESENT: PageTableFlags = PageTableFlags(1 << 0);
+    pub const WRITABLE: PageTableFlags = PageTableFlags(1 << 1);
+    pub const USER_ACCESSIBLE: PageTableFlags = PageTableFlags(1 << 2);
+    pub const WRITE_THROUGH: PageTableFlags = PageTableFlags(1 << 3);
+    pub const NO_CACHE: PageTableFlags = PageTableFlags(1 << 4);
+    pub const ACCESSED: PageTableFlags = PageTableFlags(1 << 5);
+    pub const DIRTY: PageTableFlags = PageTableFlags(1 << 6);
+    pub const HUGE_PAGE: PageTableFlags = PageTableFlags(1 << 7);
+    pub const GLOBAL: PageTableFlags = PageTableFlags(1 << 8);
+    /// Only valid if the NXE bit in the EFER register is set.
+    pub const NO_EXECUTE: PageTableFlags = PageTableFlags(1 << 63);
+
+    pub const fn empty() -> PageTableFlags {
+        PageTableFlags(0)
+    }
+
+    pub const fn from_bits_truncate(bits: u64) -> PageTableFlags {
+        PageTableFlags(bits & !ADDRESS_MASK)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn contains(&self, other: PageTableFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: PageTableFlags) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: PageTableFlags) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for PageTableFlags {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        PageTableFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for PageTableFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+impl fmt::Debug for PageTableFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const NAMES: [(PageTableFlags, &str); 10] = [
+            (PageTableFlags::PRESENT, "PRESENT"),
+            (PageTableFlags::WRITABLE, "WRITABLE"),
+            (PageTableFlags::USER_ACCESSIBLE, "USER_ACCESSIBLE"),
+            (PageTableFlags::WRITE_THROUGH, "WRITE_THROUGH"),
+            (PageTableFlags::NO_CACHE, "NO_CACHE"),
+            (PageTableFlags::ACCESSED, "ACCESSED"),
+            (PageTableFlags::DIRTY, "DIRTY"),
+            (PageTableFlags::HUGE_PAGE, "HUGE_PAGE"),
+            (PageTableFlags::GLOBAL, "GLOBAL"),
+            (PageTableFlags::NO_EXECUTE, "NO_EXECUTE"),
+        ];
+
+        let mut first = true;
+        for (flag, name) in NAMES.iter() {
+            if self.contains(*flag) {
+                if !first {
+                    f.write_str(" | ")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        if first {
+            f.write_str("(empty)")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct PageTableEntry(u64);
+
+impl PageTableEntry {
+    pub fn is_unused(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn set_unused(&mut self) {
+        self.0 = 0;
+    }
+
+    pub fn flags(&self) -> PageTableFlags {
+        PageTableFlags::from_bits_truncate(self.0)
+    }
+
+    pub fn address(&self) -> PhysAddr {
+        PhysAddr::new(self.0 & ADDRESS_MASK)
+    }
+
+    /// The 4 KiB frame the entry points to, if it is present and does not map a huge page.
+    pub fn frame(&self) -> Option<Frame> {
+        let flags = self.flags();
+        if flags.contains(PageTableFlags::PRESENT) && !flags.contains(PageTableFlags::HUGE_PAGE) {
+            Some(Frame::containing_address(self.address()))
+        } else {
+            None
+        }
+    }
+
+    pub fn set_address(&mut self, address: PhysAddr, flags: PageTableFlags) {
+        assert!(
+            address.as_u64() & !ADDRESS_MASK == 0,
+            "Address {:?} is not page aligned",
+            address
+        );
+        self.0 = address.as_u64() | flags.bits();
+    }
+}
+
+impl fmt::Debug for PageTableEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PageTableEntry")
+            .field("address", &self.address())
+            .field("flags", &self.flags())
+            .finish()
+    }
+}
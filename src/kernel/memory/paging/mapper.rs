@@ -0,0 +1,190 @@
+use core::ptr::NonNull;
+
+use super::entry::PageTableFlags;
+use super::page::{table_index, Page, PageSize, Size4KiB};
+use super::table::{PageTable, P4};
+use crate::asm::tlb;
+use crate::memory::frame::Frame;
+use crate::memory::frame_allocator::FrameAllocator;
+use crate::memory::phys_addr::PhysAddr;
+use crate::memory::virt_addr::VirtAddr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapToError {
+    /// A new page table could not be allocated.
+    FrameAllocationFailed,
+    /// An entry on the path to the page maps a huge page.
+    ParentEntryHugePage,
+    PageAlreadyMapped,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnmapError {
+    PageNotMapped,
+    /// An entry on the path to the page maps a huge page.
+    ParentEntryHugePage,
+}
+
+/// The currently loaded four-level page table, accessed through the
+/// recursive mapping in the last P4 entry.
+pub struct ActivePageTable {
+    p4: NonNull<PageTable>,
+}
+
+impl ActivePageTable {
+    /// There must only be one instance of the active page table.
+    pub const unsafe fn new() -> ActivePageTable {
+        ActivePageTable {
+            p4: NonNull::new_unchecked(P4),
+        }
+    }
+
+    fn p4(&self) -> &PageTable {
+        unsafe { self.p4.as_ref() }
+    }
+
+    fn p4_mut(&mut self) -> &mut PageTable {
+        unsafe { self.p4.as_mut() }
+    }
+
+    /// Translates a virtual address to the mapped physical address.
+    pub fn translate(&self, address: VirtAddr) -> Option<PhysAddr> {
+        let mut table = self.p4();
+
+        for level in (1..=4).rev() {
+            let entry = &table[table_index(address, level)];
+            let flags = entry.flags();
+
+            if !flags.contains(PageTableFlags::PRESENT) {
+                return None;
+            }
+
+            if level == 1 || (level < 4 && flags.contains(PageTableFlags::HUGE_PAGE)) {
+                let offset_mask = (1 << (12 + 9 * (level - 1))) - 1;
+                return Some(entry.address() + (address.as_u64() & offset_mask));
+            }
+
+            table = table.next_table(table_index(address, level))?;
+        }
+
+        unreachable!()
+    }
+
+    /// Returns the frame the page is mapped to.
+    #[allow(dead_code)]
+    pub fn translate_page<S: PageSize>(&self, page: Page<S>) -> Option<Frame<S>> {
+        let table = self.table_for(page).ok()?;
+        let entry = &table[page.table_index(S::LEVEL)];
+        let flags = entry.flags();
+        let is_huge = flags.contains(PageTableFlags::HUGE_PAGE);
+
+        if !flags.contains(PageTableFlags::PRESENT) || is_huge != (S::LEVEL > 1) {
+            return None;
+        }
+        Some(Frame::containing_address(entry.address()))
+    }
+
+    /// Maps the page to the frame with the given flags. Missing page tables
+    /// are allocated with the frame allocator.
+    pub fn map_to<S: PageSize, A: FrameAllocator>(
+        &mut self,
+        page: Page<S>,
+        frame: Frame<S>,
+        flags: PageTableFlags,
+        allocator: &mut A,
+    ) -> Result<(), MapToError> {
+        let mut table = self.p4_mut();
+        for level in (S::LEVEL + 1..=4).rev() {
+            table = table.next_table_create(page.table_index(level), allocator)?;
+        }
+
+        let entry = &mut table[page.table_index(S::LEVEL)];
+        if !entry.is_unused() {
+            return Err(MapToError::PageAlreadyMapped);
+        }
+
+        let mut flags = flags | PageTableFlags::PRESENT;
+        if S::LEVEL > 1 {
+            flags |= PageTableFlags::HUGE_PAGE;
+        }
+        entry.set_address(frame.start_address(), flags);
+
+        Ok(())
+    }
+
+    /// Maps the page to a newly allocated frame.
+    pub fn map<A: FrameAllocator>(
+        &mut self,
+        page: Page<Size4KiB>,
+        flags: PageTableFlags,
+        allocator: &mut A,
+    ) -> Result<(), MapToError> {
+        let frame = allocator
+            .allocate_frame()
+            .ok_or(MapToError::FrameAllocationFailed)?;
+        self.map_to(page, frame, flags, allocator)
+            .inspect_err(|_| allocator.deallocate_frame(frame))
+    }
+
+    /// Maps the frame to the page with the same address.
+    pub fn identity_map<S: PageSize, A: FrameAllocator>(
+        &mut self,
+        frame: Frame<S>,
+        flags: PageTableFlags,
+        allocator: &mut A,
+    ) -> Result<(), MapToError> {
+        let page = Page::containing_address(VirtAddr::new(frame.start_address().as_u64()));
+        self.map_to(page, frame, flags, allocator)
+    }
+
+    /// Removes the mapping of the page and returns the frame it was mapped to.
+    ///
+    /// The frame is not deallocated and page tables which became empty are not freed.
+    #[allow(dead_code)]
+    pub fn unmap<S: PageSize>(&mut self, page: Page<S>) -> Result<Frame<S>, UnmapError> {
+        let table = self.table_for_mut(page)?;
+        let entry = &mut table[page.table_index(S::LEVEL)];
+        let flags = entry.flags();
+
+        if !flags.contains(PageTableFlags::PRESENT) {
+            return Err(UnmapError::PageNotMapped);
+        }
+        if flags.contains(PageTableFlags::HUGE_PAGE) != (S::LEVEL > 1) {
+            return Err(UnmapError::ParentEntryHugePage);
+        }
+
+        let frame = Frame::containing_address(entry.address());
+        entry.set_unused();
+        tlb::flush(page.start_address());
+
+        Ok(frame)
+    }
+
+    /// Returns the page table which contains the entry for the page.
+    fn table_for<S: PageSize>(&self, page: Page<S>) -> Result<&PageTable, UnmapError> {
+        let mut table = self.p4();
+        for level in (S::LEVEL + 1..=4).rev() {
+            let index = page.table_index(level);
+            if table[index].flags().contains(PageTableFlags::HUGE_PAGE) {
+                return Err(UnmapError::ParentEntryHugePage);
+            }
+            table = table.next_table(index).ok_or(UnmapError::PageNotMapped)?;
+        }
+        Ok(table)
+    }
+
+    fn table_for_mut<S: PageSize>(&mut self, page: Page<S>) -> Result<&mut PageTable, UnmapError> {
+        let mut table = self.p4_mut();
+        for level in (S::LEVEL + 1..=4).rev() {
+            let index = page.table_index(level);
+            if table[index].flags().contains(PageTableFlags::HUGE_PAGE) {
+                return Err(UnmapError::ParentEntryHugePage);
+            }
+            table = table
+                .next_table_mut(index)
+                .ok_or(UnmapError::PageNotMapped)?;
+        }
+        Ok(table)
+    }
+}
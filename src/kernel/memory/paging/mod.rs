@@ -0,0 +1,33 @@
+pub mod entry;
+pub mod mapper;
+pub mod page;
+pub mod table;
+
+use self::mapper::ActivePageTable;
+use self::table::{ENTRY_COUNT, P4};
+use crate::asm::control_registers::Cr3;
+use crate::ylib::sync::mutex::Mutex;
+
+pub use self::entry::PageTableFlags;
+pub use self::page::Page;
+
+pub static ACTIVE_PAGE_TABLE: Mutex<ActivePageTable> =
+    Mutex::new(unsafe { ActivePageTable::new() });
+
+/// Checks that the P4 table set up in boot.asm is recursively mapped.
+pub fn init() {
+    let (p4_frame, _) = Cr3::read();
+    let p4 = unsafe { &*P4 };
+    let recursive_entry = &p4[ENTRY_COUNT - 1];
+
+    assert_eq!(
+        recursive_entry.frame(),
+        Some(p4_frame),
+        "P4 table is not recursively mapped"
+    );
+
+    ok!(
+        "Paging initialized with P4 at {:?}",
+        p4_frame.start_address()
+    );
+}
@@ -0,0 +1,96 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::memory::virt_addr::VirtAddr;
+
+/// Size of a page which can be mapped by a page table entry.
+pub trait PageSize: Copy + Eq + Ord + fmt::Debug {
+    const SIZE: u64;
+    /// Level of the page table which contains the entry mapping a page of this size.
+    const LEVEL: usize;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Size4KiB {}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Size2MiB {}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Size1GiB {}
+
+impl PageSize for Size4KiB {
+    const SIZE: u64 = 4096;
+    const LEVEL: usize = 1;
+}
+
+impl PageSize for Size2MiB {
+    const SIZE: u64 = Size4KiB::SIZE * 512;
+    const LEVEL: usize = 2;
+}
+
+impl PageSize for Size1GiB {
+    const SIZE: u64 = Size2MiB::SIZE * 512;
+    const LEVEL: usize = 3;
+}
+
+/// A virtual memory page, 4 KiB by default.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Page<S: PageSize = Size4KiB> {
+    start_address: VirtAddr,
+    size: PhantomData<S>,
+}
+
+impl<S: PageSize> Page<S> {
+    pub fn containing_address(address: VirtAddr) -> Page<S> {
+        Page {
+            start_address: address.align_down(S::SIZE),
+            size: PhantomData,
+        }
+    }
+
+    pub fn start_address(&self) -> VirtAddr {
+        self.start_address
+    }
+
+    /// Index into the page table of the given level (4 to 1) for this page.
+    pub fn table_index(&self, level: usize) -> usize {
+        table_index(self.start_address, level)
+    }
+
+    pub fn range_inclusive(start: Page<S>, end: Page<S>) -> PageIter<S> {
+        PageIter { start, end }
+    }
+}
+
+impl<S: PageSize> fmt::Debug for Page<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Page[{}]({:#x})", S::SIZE, self.start_address.as_u64())
+    }
+}
+
+/// Index into the page table of the given level (4 to 1) for the address.
+pub fn table_index(address: VirtAddr, level: usize) -> usize {
+    ((address.as_u64() >> (12 + 9 * (level - 1))) & 0x1ff) as usize
+}
+
+pub struct PageIter<S: PageSize> {
+    start: Page<S>,
+    end: Page<S>,
+}
+
+impl<S: PageSize> Iterator for PageIter<S> {
+    type Item = Page<S>;
+
+    fn next(&mut self) -> Option<Page<S>> {
+        if self.start <= self.end {
+            let page = self.start;
+            self.start.start_address = self.start.start_address + S::SIZE;
+            Some(page)
+        } else {
+            None
+        }
+    }
+}
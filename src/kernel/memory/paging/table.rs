@@ -0,0 +1,85 @@
+use core::ops::{Index, IndexMut};
+
+use super::entry::{PageTableEntry, PageTableFlags};
+use super::mapper::MapToError;
+use crate::memory::frame_allocator::FrameAllocator;
+
+pub const ENTRY_COUNT: usize = 512;
+
+/// Address of the P4 table through the recursive mapping in its last entry.
+pub const P4: *mut PageTable = 0xffff_ffff_ffff_f000 as *mut _;
+
+#[repr(C, align(4096))]
+pub struct PageTable {
+    entries: [PageTableEntry; ENTRY_COUNT],
+}
+
+impl PageTable {
+    pub fn zero(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.set_unused();
+        }
+    }
+
+    /// Returns the virtual address of the table the entry with the given index
+    /// points to. Only valid if the table itself was accessed through the
+    /// recursive mapping.
+    fn next_table_address(&self, index: usize) -> Option<usize> {
+        let flags = self[index].flags();
+        if flags.contains(PageTableFlags::PRESENT) && !flags.contains(PageTableFlags::HUGE_PAGE) {
+            let table_address = self as *const _ as usize;
+            Some((table_address << 9) | (index << 12))
+        } else {
+            None
+        }
+    }
+
+    pub fn next_table(&self, index: usize) -> Option<&PageTable> {
+        self.next_table_address(index)
+            .map(|address| unsafe { &*(address as *const _) })
+    }
+
+    pub fn next_table_mut(&mut self, index: usize) -> Option<&mut PageTable> {
+        self.next_table_address(index)
+            .map(|address| unsafe { &mut *(address as *mut _) })
+    }
+
+    /// Returns the next table and creates it if the entry is unused.
+    pub fn next_table_create<A: FrameAllocator>(
+        &mut self,
+        index: usize,
+        allocator: &mut A,
+    ) -> Result<&mut PageTable, MapToError> {
+        if self[index].flags().contains(PageTableFlags::HUGE_PAGE) {
+            return Err(MapToError::ParentEntryHugePage);
+        }
+
+        if self[index].is_unused() {
+            let frame = allocator
+                .allocate_frame()
+                .ok_or(MapToError::FrameAllocationFailed)?;
+            self[index].set_address(
+                frame.start_address(),
+                PageTableFlags::PRESENT | PageTableFlags::WRITABLE,
+            );
+            let table = self.next_table_mut(index).unwrap();
+            table.zero();
+            return Ok(table);
+        }
+
+        Ok(self.next_table_mut(index).unwrap())
+    }
+}
+
+impl Index<usize> for PageTable {
+    type Output = PageTableEntry;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.entries[index]
+    }
+}
+
+impl IndexMut<usize> for PageTable {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.entries[index]
+    }
+}
@@ -0,0 +1,78 @@
+use core::fmt;
+use core::ops::{Add, Sub};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct PhysAddr(u64);
+
+impl PhysAddr {
+    pub fn new(addr: u64) -> PhysAddr {
+        Self::try_new(addr).expect(
+            "address passed to PhysAddr::new must not contain any data \
+             in bits 52 to 64",
+        )
+    }
+
+    pub fn zero() -> PhysAddr {
+        PhysAddr(0)
+    }
+
+    pub fn try_new(addr: u64) -> Result<PhysAddr, PhysAddrNotValid> {
+        match addr & (u64::MAX << 52) {
+            0 => Ok(PhysAddr(addr)),
+            other => Err(PhysAddrNotValid(other)),
+        }
+    }
+
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Aligns the address downwards to the given alignment (must be a power of two).
+    pub fn align_down(self, align: u64) -> PhysAddr {
+        PhysAddr(self.0 & !(align - 1))
+    }
+
+    /// Aligns the address upwards to the given alignment (must be a power of two).
+    pub fn align_up(self, align: u64) -> PhysAddr {
+        PhysAddr::new((self.0 + align - 1) & !(align - 1))
+    }
+
+    pub fn is_aligned(&self, align: u64) -> bool {
+        self.0 & (align - 1) == 0
+    }
+}
+
+impl fmt::Debug for PhysAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PhysAddr(0x{:x})", self.0)
+    }
+}
+
+impl Add<u64> for PhysAddr {
+    type Output = Self;
+    fn add(self, rhs: u64) -> Self::Output {
+        PhysAddr::new(self.0 + rhs)
+    }
+}
+
+impl Add<usize> for PhysAddr {
+    type Output = Self;
+    fn add(self, rhs: usize) -> Self::Output {
+        self + rhs as u64
+    }
+}
+
+impl Sub<PhysAddr> for PhysAddr {
+    type Output = u64;
+    fn sub(self, rhs: PhysAddr) -> Self::Output {
+        self.0 - rhs.0
+    }
+}
+
+/// A passed `u64` was not a valid physical address.
+///
+/// This means that bits 52 to 64 were not all null. The x86_64 architecture
+/// supports at most 52 bits of physical address space.
+#[derive(Debug)]
+pub struct PhysAddrNotValid(u64);
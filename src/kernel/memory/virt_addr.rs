@@ -35,6 +35,28 @@ impl VirtAddr {
     pub fn from_ptr<T>(ptr: *const T) -> VirtAddr {
         VirtAddr::new(ptr as u64)
     }
+
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    pub fn as_ptr<T>(&self) -> *const T {
+        self.0 as *const T
+    }
+
+    pub fn as_mut_ptr<T>(&self) -> *mut T {
+        self.0 as *mut T
+    }
+
+    /// Aligns the address downwards to the given alignment (must be a power of two).
+    pub fn align_down(self, align: u64) -> VirtAddr {
+        VirtAddr(self.0 & !(align - 1))
+    }
+
+    /// Aligns the address upwards to the given alignment (must be a power of two).
+    pub fn align_up(self, align: u64) -> VirtAddr {
+        VirtAddr::new((self.0 + align - 1) & !(align - 1))
+    }
 }
 
 impl fmt::Debug for VirtAddr {
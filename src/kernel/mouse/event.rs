@@ -0,0 +1,20 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MouseButtons {
+    pub left: bool,
+    pub right: bool,
+    pub middle: bool,
+    /// Only reported by five button mice.
+    pub fourth: bool,
+    pub fifth: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    /// Relative motion, positive to the right.
+    pub dx: i16,
+    /// Relative motion, positive upwards.
+    pub dy: i16,
+    /// Scroll wheel steps, positive towards the user. Always 0 without wheel.
+    pub wheel: i8,
+    pub buttons: MouseButtons,
+}
@@ -0,0 +1,124 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use self::event::MouseEvent;
+use self::packet::{PacketDecoder, PacketFormat};
+use crate::interrupts::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::interrupts::irq::{self, IrqError, IrqReturn};
+use crate::ps2::controller::{Controller, Ps2Port};
+use crate::ps2::device::{self, DeviceError, DeviceType};
+use crate::ps2::{self, Ps2Error};
+use crate::ylib::sync::mutex::Mutex;
+use crate::ylib::sync::ring_buffer::RingBuffer;
+
+pub mod event;
+pub mod packet;
+
+const EVENT_BUFFER_SIZE: usize = 64;
+const SAMPLE_RATE: u8 = 100;
+/// Sample rates which unlock the scroll wheel of IntelliMouse compatible mice.
+const INTELLIMOUSE_SEQUENCE: [u8; 3] = [200, 100, 80];
+/// Sample rates which unlock the fourth and fifth button afterwards.
+const INTELLIMOUSE_EXPLORER_SEQUENCE: [u8; 3] = [200, 200, 80];
+
+/// Only used by the interrupt handler after the initialization.
+static DECODER: Mutex<PacketDecoder> = Mutex::new(PacketDecoder::new());
+static EVENTS: RingBuffer<MouseEvent, EVENT_BUFFER_SIZE> = RingBuffer::new();
+/// Events which were dropped because nobody read them.
+static DROPPED_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseError {
+    /// There is no mouse on the second PS/2 port.
+    NotPresent,
+    Ps2(Ps2Error),
+    Irq(IrqError),
+}
+
+/// Detects the scroll wheel, enables data reporting and the interrupt of the
+/// second port and starts assembling the packets of IRQ12.
+pub fn init() -> Result<(), MouseError> {
+    if !ps2::has_mouse() {
+        return Err(MouseError::NotPresent);
+    }
+
+    let device_type = ps2::with_first_port_disabled(|controller| {
+        let device_type =
+            configure(controller).map_err(|error| Ps2Error::Device(Ps2Port::Second, error))?;
+        controller.set_interrupt(Ps2Port::Second, true)?;
+        Ok(device_type)
+    })
+    .map_err(MouseError::Ps2)?;
+
+    let format = match device_type {
+        DeviceType::IntelliMouse => PacketFormat::IntelliMouse,
+        DeviceType::IntelliMouseExplorer => PacketFormat::IntelliMouseExplorer,
+        _ => PacketFormat::Standard,
+    };
+    DECODER.lock().set_format(format);
+    ps2::set_device(Ps2Port::Second, device_type);
+
+    irq::register_irq(irq::MOUSE_IRQ, mouse_handler).map_err(MouseError::Irq)?;
+    ok!(
+        "Mouse initialized as {:?} with {} byte packets",
+        device_type,
+        format.size()
+    );
+    Ok(())
+}
+
+/// Takes the oldest mouse event which was not read yet.
+#[allow(dead_code)]
+pub fn read_event() -> Option<MouseEvent> {
+    EVENTS.pop()
+}
+
+#[allow(dead_code)]
+pub fn dropped_events() -> u64 {
+    DROPPED_EVENTS.load(Ordering::SeqCst)
+}
+
+/// Unlocks the extensions of the mouse, which change its type, and enables
+/// data reporting.
+fn configure(controller: &Controller) -> Result<DeviceType, DeviceError> {
+    let mut device_type = knock(controller, INTELLIMOUSE_SEQUENCE)?;
+    if device_type == DeviceType::IntelliMouse {
+        device_type = knock(controller, INTELLIMOUSE_EXPLORER_SEQUENCE)?;
+    }
+
+    let port = Ps2Port::Second;
+    device::command_with_data(
+        controller,
+        port,
+        device::COMMAND_SET_SAMPLE_RATE,
+        SAMPLE_RATE,
+    )?;
+    device::command(controller, port, device::COMMAND_ENABLE_SCANNING)?;
+    Ok(device_type)
+}
+
+/// Sets the sample rates of the sequence and identifies the mouse again.
+fn knock(controller: &Controller, sequence: [u8; 3]) -> Result<DeviceType, DeviceError> {
+    for &rate in sequence.iter() {
+        device::command_with_data(
+            controller,
+            Ps2Port::Second,
+            device::COMMAND_SET_SAMPLE_RATE,
+            rate,
+        )?;
+    }
+    device::identify(controller, Ps2Port::Second)
+}
+
+fn mouse_handler(_stack_frame: &InterruptStackFrame) -> IrqReturn {
+    let byte = match ps2::CONTROLLER.read_interrupt_data(Ps2Port::Second) {
+        Some(byte) => byte,
+        None => return IrqReturn::Handled,
+    };
+
+    if let Some(event) = DECODER.lock().add_byte(byte) {
+        if EVENTS.push(event).is_err() {
+            DROPPED_EVENTS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    IrqReturn::Handled
+}
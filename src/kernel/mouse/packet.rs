@@ -0,0 +1,112 @@
+use super::event::{MouseButtons, MouseEvent};
+
+// First byte of a packet
+const LEFT_BUTTON: u8 = 1 << 0;
+const RIGHT_BUTTON: u8 = 1 << 1;
+const MIDDLE_BUTTON: u8 = 1 << 2;
+/// Set in every first byte, used to find the start of a packet.
+const ALWAYS_ONE: u8 = 1 << 3;
+const X_SIGN: u8 = 1 << 4;
+const Y_SIGN: u8 = 1 << 5;
+const X_OVERFLOW: u8 = 1 << 6;
+const Y_OVERFLOW: u8 = 1 << 7;
+
+// Fourth byte of five button mice
+const WHEEL_MASK: u8 = 0x0f;
+const FOURTH_BUTTON: u8 = 1 << 4;
+const FIFTH_BUTTON: u8 = 1 << 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketFormat {
+    /// Buttons and motion in 3 bytes.
+    Standard,
+    /// Fourth byte with the wheel motion.
+    IntelliMouse,
+    /// Fourth byte with the wheel motion and two more buttons.
+    IntelliMouseExplorer,
+}
+
+impl PacketFormat {
+    pub fn size(&self) -> usize {
+        match self {
+            PacketFormat::Standard => 3,
+            PacketFormat::IntelliMouse | PacketFormat::IntelliMouseExplorer => 4,
+        }
+    }
+}
+
+/// Assembles the bytes of IRQ12 into mouse events.
+pub struct PacketDecoder {
+    format: PacketFormat,
+    bytes: [u8; 4],
+    length: usize,
+}
+
+impl PacketDecoder {
+    pub const fn new() -> Self {
+        PacketDecoder {
+            format: PacketFormat::Standard,
+            bytes: [0; 4],
+            length: 0,
+        }
+    }
+
+    pub fn set_format(&mut self, format: PacketFormat) {
+        self.format = format;
+        self.length = 0;
+    }
+
+    pub fn add_byte(&mut self, byte: u8) -> Option<MouseEvent> {
+        // Drop bytes until the start of the next packet after losing sync
+        if self.length == 0 && byte & ALWAYS_ONE == 0 {
+            return None;
+        }
+
+        self.bytes[self.length] = byte;
+        self.length += 1;
+        if self.length < self.format.size() {
+            return None;
+        }
+        self.length = 0;
+        Some(self.decode())
+    }
+
+    fn decode(&self) -> MouseEvent {
+        let flags = self.bytes[0];
+        let motion = |value: u8, sign: u8, overflow: u8| {
+            if flags & overflow != 0 {
+                0
+            } else if flags & sign != 0 {
+                value as i16 - 0x100
+            } else {
+                value as i16
+            }
+        };
+
+        let mut buttons = MouseButtons {
+            left: flags & LEFT_BUTTON != 0,
+            right: flags & RIGHT_BUTTON != 0,
+            middle: flags & MIDDLE_BUTTON != 0,
+            fourth: false,
+            fifth: false,
+        };
+        let wheel = match self.format {
+            PacketFormat::Standard => 0,
+            PacketFormat::IntelliMouse => self.bytes[3] as i8,
+            PacketFormat::IntelliMouseExplorer => {
+                let extra = self.bytes[3];
+                buttons.fourth = extra & FOURTH_BUTTON != 0;
+                buttons.fifth = extra & FIFTH_BUTTON != 0;
+                // Sign extend the 4 bit wheel motion
+                ((extra & WHEEL_MASK) << 4) as i8 >> 4
+            }
+        };
+
+        MouseEvent {
+            dx: motion(self.bytes[1], X_SIGN, X_OVERFLOW),
+            dy: motion(self.bytes[2], Y_SIGN, Y_OVERFLOW),
+            wheel,
+            buttons,
+        }
+    }
+}
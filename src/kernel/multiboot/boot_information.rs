@@ -0,0 +1,138 @@
+use core::fmt;
+
+use super::boot_loader_name::BootLoaderNameTag;
+use super::command_line::CommandLineTag;
+use super::elf_sections::ElfSectionsTag;
+use super::framebuffer::FramebufferTag;
+use super::memory_map::MemoryMapTag;
+use super::module::ModuleIter;
+use super::rsdp::RsdpTag;
+use super::tag::{Tag, TagIter, TagType};
+
+#[repr(C)]
+struct BootInformationHeader {
+    total_size: u32,
+    reserved: u32,
+}
+
+/// The boot information structure the multiboot2 bootloader passes to the kernel.
+#[derive(Clone, Copy)]
+pub struct BootInformation {
+    header: &'static BootInformationHeader,
+}
+
+impl BootInformation {
+    /// Loads the boot information structure at the given physical address.
+    ///
+    /// The address must point to a valid multiboot2 boot information structure
+    /// which is identity mapped.
+    pub unsafe fn load(address: usize) -> Result<BootInformation, BootInformationInvalid> {
+        if address == 0 || address & 0b111 != 0 {
+            return Err(BootInformationInvalid::Alignment(address));
+        }
+
+        let header = &*(address as *const BootInformationHeader);
+        let total_size = header.total_size as usize;
+
+        if total_size < core::mem::size_of::<BootInformationHeader>() + 8 || total_size & 0b111 != 0
+        {
+            return Err(BootInformationInvalid::Size(total_size));
+        }
+
+        // The last tag must always be the end tag
+        let end_tag = &*((address + total_size - 8) as *const Tag);
+        if !end_tag.is(TagType::End) || end_tag.size != 8 {
+            return Err(BootInformationInvalid::MissingEndTag);
+        }
+
+        Ok(BootInformation { header })
+    }
+
+    pub fn start_address(&self) -> usize {
+        self.header as *const BootInformationHeader as usize
+    }
+
+    /// Address of the first byte after the boot information.
+    pub fn end_address(&self) -> usize {
+        self.start_address() + self.total_size()
+    }
+
+    pub fn total_size(&self) -> usize {
+        self.header.total_size as usize
+    }
+
+    pub fn memory_map_tag(&self) -> Option<&'static MemoryMapTag> {
+        self.get_tag(TagType::MemoryMap)
+            .map(|tag| unsafe { tag.cast::<MemoryMapTag>() })
+    }
+
+    pub fn elf_sections_tag(&self) -> Option<&'static ElfSectionsTag> {
+        self.get_tag(TagType::ElfSections)
+            .map(|tag| unsafe { tag.cast::<ElfSectionsTag>() })
+    }
+
+    pub fn command_line_tag(&self) -> Option<&'static CommandLineTag> {
+        self.get_tag(TagType::CommandLine)
+            .map(|tag| unsafe { tag.cast::<CommandLineTag>() })
+    }
+
+    pub fn boot_loader_name_tag(&self) -> Option<&'static BootLoaderNameTag> {
+        self.get_tag(TagType::BootLoaderName)
+            .map(|tag| unsafe { tag.cast::<BootLoaderNameTag>() })
+    }
+
+    pub fn module_tags(&self) -> ModuleIter {
+        ModuleIter::new(self.tags())
+    }
+
+    pub fn framebuffer_tag(&self) -> Option<FramebufferTag> {
+        self.get_tag(TagType::Framebuffer)
+            .and_then(|tag| unsafe { FramebufferTag::from_tag(tag) })
+    }
+
+    /// Prefers the RSDP of ACPI 2.0 over the one of ACPI 1.0.
+    pub fn rsdp_tag(&self) -> Option<&'static RsdpTag> {
+        self.get_tag(TagType::AcpiNew)
+            .or_else(|| self.get_tag(TagType::AcpiOld))
+            .map(|tag| unsafe { tag.cast::<RsdpTag>() })
+    }
+
+    fn get_tag(&self, typ: TagType) -> Option<&'static Tag> {
+        self.tags().find(|tag| tag.is(typ))
+    }
+
+    fn tags(&self) -> TagIter {
+        let first = self.start_address() + core::mem::size_of::<BootInformationHeader>();
+        TagIter::new(first as *const Tag, self.end_address() as *const u8)
+    }
+}
+
+impl fmt::Debug for BootInformation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("BootInformation");
+
+        s.field(
+            "start_address",
+            &format_args!("{:#x}", self.start_address()),
+        );
+        s.field("total_size", &format_args!("{:#x}", self.total_size()));
+        s.field(
+            "boot_loader_name",
+            &self.boot_loader_name_tag().map(|tag| tag.name()),
+        );
+        s.field(
+            "command_line",
+            &self.command_line_tag().map(|tag| tag.command_line()),
+        );
+        s.field("framebuffer", &self.framebuffer_tag());
+        s.finish()
+    }
+}
+
+/// The passed address does not point to a valid boot information structure.
+#[derive(Debug)]
+pub enum BootInformationInvalid {
+    Alignment(usize),
+    Size(usize),
+    MissingEndTag,
+}
@@ -0,0 +1,16 @@
+use core::str::Utf8Error;
+
+use super::tag::{string_from_tag, Tag};
+
+/// The name of the bootloader which loaded the kernel.
+#[repr(C)]
+pub struct BootLoaderNameTag {
+    tag: Tag,
+    string: u8,
+}
+
+impl BootLoaderNameTag {
+    pub fn name(&self) -> Result<&'static str, Utf8Error> {
+        unsafe { string_from_tag(&self.tag, core::mem::size_of::<Tag>()) }
+    }
+}
@@ -0,0 +1,28 @@
+use core::str::Utf8Error;
+
+use super::tag::{string_from_tag, Tag};
+
+/// The command line which was passed to the kernel by the bootloader.
+#[repr(C)]
+pub struct CommandLineTag {
+    tag: Tag,
+    string: u8,
+}
+
+impl CommandLineTag {
+    pub fn command_line(&self) -> Result<&'static str, Utf8Error> {
+        unsafe { string_from_tag(&self.tag, core::mem::size_of::<Tag>()) }
+    }
+}
+
+/// Returns the value of a `name=value` argument of the command line.
+pub fn argument<'a>(command_line: &'a str, name: &str) -> Option<&'a str> {
+    command_line.split_whitespace().find_map(|argument| {
+        let (key, value) = argument.split_once('=')?;
+        if key == name {
+            Some(value)
+        } else {
+            None
+        }
+    })
+}
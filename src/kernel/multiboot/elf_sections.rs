@@ -0,0 +1,156 @@
+use core::fmt;
+use core::str::Utf8Error;
+
+use super::tag::Tag;
+
+const SECTION_TYPE_UNUSED: u32 = 0;
+
+#[allow(dead_code)]
+const FLAG_WRITABLE: u64 = 0x1;
+const FLAG_ALLOCATED: u64 = 0x2;
+#[allow(dead_code)]
+const FLAG_EXECUTABLE: u64 = 0x4;
+
+/// Contains the section header table of the kernel ELF image.
+#[repr(C, packed)]
+pub struct ElfSectionsTag {
+    tag: Tag,
+    number_of_sections: u32,
+    entry_size: u32,
+    string_table_index: u32,
+    first_section: [u8; 0],
+}
+
+impl ElfSectionsTag {
+    pub fn sections(&self) -> ElfSectionIter {
+        let first_section = self.first_section.as_ptr();
+        let entry_size = self.entry_size as usize;
+        let string_section = unsafe {
+            let header = first_section.add(self.string_table_index as usize * entry_size)
+                as *const ElfSectionHeader;
+            (*header).address as *const u8
+        };
+
+        ElfSectionIter {
+            current_section: first_section,
+            remaining_sections: self.number_of_sections,
+            entry_size,
+            string_section,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ElfSectionIter {
+    current_section: *const u8,
+    remaining_sections: u32,
+    entry_size: usize,
+    string_section: *const u8,
+}
+
+impl Iterator for ElfSectionIter {
+    type Item = ElfSection;
+
+    fn next(&mut self) -> Option<ElfSection> {
+        while self.remaining_sections != 0 {
+            let section = ElfSection {
+                header: self.current_section as *const ElfSectionHeader,
+                string_section: self.string_section,
+            };
+
+            self.current_section = unsafe { self.current_section.add(self.entry_size) };
+            self.remaining_sections -= 1;
+
+            if section.section_type() != SECTION_TYPE_UNUSED {
+                return Some(section);
+            }
+        }
+        None
+    }
+}
+
+/// Section header of a 64 bit ELF file
+#[repr(C, packed)]
+struct ElfSectionHeader {
+    name_index: u32,
+    typ: u32,
+    flags: u64,
+    address: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    info: u32,
+    address_align: u64,
+    entry_size: u64,
+}
+
+pub struct ElfSection {
+    header: *const ElfSectionHeader,
+    string_section: *const u8,
+}
+
+impl ElfSection {
+    fn header(&self) -> &ElfSectionHeader {
+        unsafe { &*self.header }
+    }
+
+    pub fn name(&self) -> Result<&'static str, Utf8Error> {
+        unsafe {
+            let start = self.string_section.add(self.header().name_index as usize);
+            let mut length = 0;
+            while *start.add(length) != 0 {
+                length += 1;
+            }
+            core::str::from_utf8(core::slice::from_raw_parts(start, length))
+        }
+    }
+
+    pub fn section_type(&self) -> u32 {
+        self.header().typ
+    }
+
+    pub fn start_address(&self) -> u64 {
+        self.header().address
+    }
+
+    /// Address of the first byte after the section.
+    pub fn end_address(&self) -> u64 {
+        self.start_address() + self.size()
+    }
+
+    pub fn size(&self) -> u64 {
+        self.header().size
+    }
+
+    #[allow(dead_code)]
+    pub fn alignment(&self) -> u64 {
+        self.header().address_align
+    }
+
+    pub fn is_allocated(&self) -> bool {
+        self.header().flags & FLAG_ALLOCATED != 0
+    }
+
+    #[allow(dead_code)]
+    pub fn is_writable(&self) -> bool {
+        self.header().flags & FLAG_WRITABLE != 0
+    }
+
+    #[allow(dead_code)]
+    pub fn is_executable(&self) -> bool {
+        self.header().flags & FLAG_EXECUTABLE != 0
+    }
+}
+
+impl fmt::Debug for ElfSection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ElfSection({:?}, {:#x}..{:#x}, flags: {:#x})",
+            self.name().unwrap_or("<invalid>"),
+            self.start_address(),
+            self.end_address(),
+            { self.header().flags }
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use super::tag::Tag;
+
+const FRAMEBUFFER_TYPE_INDEXED: u8 = 0;
+const FRAMEBUFFER_TYPE_RGB: u8 = 1;
+const FRAMEBUFFER_TYPE_TEXT: u8 = 2;
+
+#[repr(C, packed)]
+struct FramebufferTagInner {
+    tag: Tag,
+    address: u64,
+    pitch: u32,
+    width: u32,
+    height: u32,
+    bpp: u8,
+    typ: u8,
+    reserved: u16,
+    color_info: [u8; 0],
+}
+
+/// Describes the framebuffer which was set up by the bootloader.
+#[derive(Debug)]
+pub struct FramebufferTag {
+    /// Physical address of the framebuffer.
+    pub address: u64,
+    /// Number of bytes per row.
+    pub pitch: u32,
+    /// Width in pixels (or characters in text mode).
+    pub width: u32,
+    /// Height in pixels (or characters in text mode).
+    pub height: u32,
+    /// Bits per pixel.
+    pub bpp: u8,
+    pub buffer_type: FramebufferType,
+}
+
+#[derive(Debug)]
+pub enum FramebufferType {
+    Indexed {
+        palette: &'static [FramebufferColor],
+    },
+    Rgb {
+        red: FramebufferField,
+        green: FramebufferField,
+        blue: FramebufferField,
+    },
+    Text,
+}
+
+/// Position and size of one color channel inside a pixel.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FramebufferField {
+    pub position: u8,
+    pub size: u8,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FramebufferColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl FramebufferTag {
+    /// Parses the framebuffer tag. Returns `None` for unknown framebuffer types.
+    pub unsafe fn from_tag(tag: &Tag) -> Option<FramebufferTag> {
+        let inner = &*(tag as *const Tag as *const FramebufferTagInner);
+        let color_info = inner.color_info.as_ptr();
+
+        let buffer_type = match inner.typ {
+            FRAMEBUFFER_TYPE_INDEXED => {
+                // GRUB stores the number of palette entries as u16
+                let number_of_colors = *(color_info as *const u16);
+                let palette = core::slice::from_raw_parts(
+                    color_info.add(2) as *const FramebufferColor,
+                    number_of_colors as usize,
+                );
+                FramebufferType::Indexed { palette }
+            }
+            FRAMEBUFFER_TYPE_RGB => {
+                let fields = color_info as *const FramebufferField;
+                FramebufferType::Rgb {
+                    red: *fields,
+                    green: *fields.add(1),
+                    blue: *fields.add(2),
+                }
+            }
+            FRAMEBUFFER_TYPE_TEXT => FramebufferType::Text,
+            _ => return None,
+        };
+
+        Some(FramebufferTag {
+            address: inner.address,
+            pitch: inner.pitch,
+            width: inner.width,
+            height: inner.height,
+            bpp: inner.bpp,
+            buffer_type,
+        })
+    }
+}
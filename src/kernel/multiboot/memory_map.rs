@@ -0,0 +1,106 @@
+use core::fmt;
+
+use super::tag::Tag;
+
+/// The memory map provided by the bootloader (BIOS e820 map).
+#[repr(C)]
+pub struct MemoryMapTag {
+    tag: Tag,
+    entry_size: u32,
+    entry_version: u32,
+    first_area: [MemoryArea; 0],
+}
+
+impl MemoryMapTag {
+    /// All memory areas, including the reserved ones.
+    pub fn memory_areas(&self) -> MemoryAreaIter {
+        let start = self.first_area.as_ptr() as usize;
+        let end = self as *const MemoryMapTag as usize + self.tag.size as usize;
+        MemoryAreaIter {
+            current: start,
+            end,
+            entry_size: self.entry_size as usize,
+        }
+    }
+
+    /// Memory areas which can be used freely by the kernel.
+    pub fn available_areas(&self) -> impl Iterator<Item = &'static MemoryArea> {
+        self.memory_areas()
+            .filter(|area| area.area_type() == MemoryAreaType::Available)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryAreaType {
+    Available,
+    Reserved,
+    AcpiReclaimable,
+    AcpiNvs,
+    Defective,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct MemoryArea {
+    base_address: u64,
+    length: u64,
+    typ: u32,
+    reserved: u32,
+}
+
+impl MemoryArea {
+    pub fn start_address(&self) -> u64 {
+        self.base_address
+    }
+
+    /// Address of the first byte after the area.
+    pub fn end_address(&self) -> u64 {
+        self.base_address + self.length
+    }
+
+    pub fn size(&self) -> u64 {
+        self.length
+    }
+
+    pub fn area_type(&self) -> MemoryAreaType {
+        match self.typ {
+            1 => MemoryAreaType::Available,
+            3 => MemoryAreaType::AcpiReclaimable,
+            4 => MemoryAreaType::AcpiNvs,
+            5 => MemoryAreaType::Defective,
+            // All other values have to be treated as reserved
+            _ => MemoryAreaType::Reserved,
+        }
+    }
+}
+
+impl fmt::Debug for MemoryArea {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MemoryArea({:#x}..{:#x}, {:?})",
+            self.start_address(),
+            self.end_address(),
+            self.area_type()
+        )
+    }
+}
+
+pub struct MemoryAreaIter {
+    current: usize,
+    end: usize,
+    entry_size: usize,
+}
+
+impl Iterator for MemoryAreaIter {
+    type Item = &'static MemoryArea;
+
+    fn next(&mut self) -> Option<&'static MemoryArea> {
+        if self.entry_size == 0 || self.current + self.entry_size > self.end {
+            return None;
+        }
+        let area = unsafe { &*(self.current as *const MemoryArea) };
+        self.current += self.entry_size;
+        Some(area)
+    }
+}
@@ -0,0 +1,24 @@
+pub mod boot_information;
+pub mod boot_loader_name;
+pub mod command_line;
+pub mod elf_sections;
+pub mod framebuffer;
+pub mod memory_map;
+pub mod module;
+pub mod rsdp;
+pub mod tag;
+
+pub use boot_information::BootInformation;
+
+use crate::ylib::sync::mutex::Mutex;
+
+/// The boot information stays in reserved frames, so it can be kept around.
+static BOOT_INFORMATION: Mutex<Option<BootInformation>> = Mutex::new(None);
+
+pub fn set_boot_information(boot_information: BootInformation) {
+    *BOOT_INFORMATION.lock() = Some(boot_information);
+}
+
+pub fn boot_information() -> Option<BootInformation> {
+    *BOOT_INFORMATION.lock()
+}
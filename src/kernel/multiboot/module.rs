@@ -0,0 +1,67 @@
+use core::fmt;
+use core::str::Utf8Error;
+
+use super::tag::{string_from_tag, Tag, TagIter, TagType};
+
+/// A boot module (e.g. an initial ramdisk) which was loaded by the bootloader.
+#[repr(C)]
+pub struct ModuleTag {
+    tag: Tag,
+    module_start: u32,
+    module_end: u32,
+    string: u8,
+}
+
+impl ModuleTag {
+    pub fn start_address(&self) -> u64 {
+        self.module_start as u64
+    }
+
+    /// Address of the first byte after the module.
+    pub fn end_address(&self) -> u64 {
+        self.module_end as u64
+    }
+
+    #[allow(dead_code)]
+    pub fn size(&self) -> u64 {
+        self.end_address() - self.start_address()
+    }
+
+    pub fn command_line(&self) -> Result<&'static str, Utf8Error> {
+        // Skip the tag header and the start and end address
+        unsafe { string_from_tag(&self.tag, core::mem::size_of::<Tag>() + 8) }
+    }
+}
+
+impl fmt::Debug for ModuleTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ModuleTag")
+            .field(
+                "start_address",
+                &format_args!("{:#x}", self.start_address()),
+            )
+            .field("end_address", &format_args!("{:#x}", self.end_address()))
+            .field("command_line", &self.command_line())
+            .finish()
+    }
+}
+
+pub struct ModuleIter {
+    tags: TagIter,
+}
+
+impl ModuleIter {
+    pub fn new(tags: TagIter) -> Self {
+        ModuleIter { tags }
+    }
+}
+
+impl Iterator for ModuleIter {
+    type Item = &'static ModuleTag;
+
+    fn next(&mut self) -> Option<&'static ModuleTag> {
+        self.tags
+            .find(|tag| tag.is(TagType::Module))
+            .map(|tag| unsafe { tag.cast::<ModuleTag>() })
+    }
+}
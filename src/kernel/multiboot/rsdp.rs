@@ -0,0 +1,21 @@
+use super::tag::{Tag, TagType};
+
+/// A copy of the ACPI root system description pointer made by the bootloader.
+#[repr(C)]
+pub struct RsdpTag {
+    tag: Tag,
+    rsdp: u8,
+}
+
+impl RsdpTag {
+    /// Address of the copied RSDP, which is part of the boot information.
+    pub fn rsdp_address(&self) -> usize {
+        &self.rsdp as *const u8 as usize
+    }
+
+    /// True for the RSDP of ACPI 2.0 and later, which contains the XSDT address.
+    #[allow(dead_code)]
+    pub fn is_extended(&self) -> bool {
+        self.tag.is(TagType::AcpiNew)
+    }
+}
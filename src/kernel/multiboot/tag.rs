@@ -0,0 +1,98 @@
+use core::str::Utf8Error;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum TagType {
+    End = 0,
+    CommandLine = 1,
+    BootLoaderName = 2,
+    Module = 3,
+    BasicMemoryInformation = 4,
+    BiosBootDevice = 5,
+    MemoryMap = 6,
+    Vbe = 7,
+    Framebuffer = 8,
+    ElfSections = 9,
+    Apm = 10,
+    Efi32 = 11,
+    Efi64 = 12,
+    Smbios = 13,
+    AcpiOld = 14,
+    AcpiNew = 15,
+    Network = 16,
+    EfiMemoryMap = 17,
+    EfiBootServicesNotTerminated = 18,
+    Efi32ImageHandle = 19,
+    Efi64ImageHandle = 20,
+    ImageLoadBaseAddress = 21,
+}
+
+/// Header every multiboot2 tag starts with.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tag {
+    pub typ: u32,
+    pub size: u32,
+}
+
+impl Tag {
+    pub fn is(&self, typ: TagType) -> bool {
+        self.typ == typ as u32
+    }
+
+    /// Reinterprets the tag as the concrete tag structure `T`.
+    ///
+    /// The caller must make sure that the type of the tag matches `T`.
+    pub unsafe fn cast<T>(&self) -> &'static T {
+        &*(self as *const Tag as *const T)
+    }
+}
+
+/// Iterates over all tags until the end tag or the end of the boot information is reached.
+pub struct TagIter {
+    current: *const Tag,
+    end: *const u8,
+}
+
+impl TagIter {
+    pub fn new(first: *const Tag, end: *const u8) -> Self {
+        TagIter {
+            current: first,
+            end,
+        }
+    }
+}
+
+impl Iterator for TagIter {
+    type Item = &'static Tag;
+
+    fn next(&mut self) -> Option<&'static Tag> {
+        // A tag header must completely fit into the boot information
+        if self.current as usize + core::mem::size_of::<Tag>() > self.end as usize {
+            return None;
+        }
+
+        let tag = unsafe { &*self.current };
+
+        if tag.is(TagType::End) || (tag.size as usize) < core::mem::size_of::<Tag>() {
+            return None;
+        }
+
+        // Tags are padded to an 8 byte alignment
+        let next = self.current as usize + ((tag.size as usize + 7) & !7);
+        self.current = next as *const Tag;
+
+        Some(tag)
+    }
+}
+
+/// Reads the null terminated UTF-8 string which starts at `offset` bytes into the given tag.
+pub unsafe fn string_from_tag(tag: &Tag, offset: usize) -> Result<&'static str, Utf8Error> {
+    let start = (tag as *const Tag as *const u8).add(offset);
+    let max_length = (tag.size as usize).saturating_sub(offset);
+    let bytes = core::slice::from_raw_parts(start, max_length);
+
+    let length = bytes.iter().position(|&b| b == 0).unwrap_or(max_length);
+    core::str::from_utf8(&bytes[..length])
+}
@@ -1,4 +1,4 @@
-use crate::{asm::Port, interrupts::interrupt_descriptor_table::table::InterruptType};
+use crate::asm::Port;
 
 // Command sent to begin PIC initialization.
 const CMD_INIT: u8 = 0x11;
@@ -6,6 +6,14 @@ const CMD_INIT: u8 = 0x11;
 /// Command sent to acknowledge an interrupt.
 const CMD_END_OF_INTERRUPT: u8 = 0x20;
 
+// OCW3 commands to select the register returned by the next command port read.
+#[allow(dead_code)]
+const CMD_READ_IRR: u8 = 0x0a;
+const CMD_READ_ISR: u8 = 0x0b;
+
+/// Line of the master PIC the slave PIC is connected to.
+const CASCADE_IRQ: u8 = 2;
+
 // The mode in which we want to run our PICs.
 const MODE_8086: u8 = 0x01;
 
@@ -32,6 +40,23 @@ impl PIC {
             self.command.write(CMD_END_OF_INTERRUPT);
         }
     }
+
+    fn read_mask(&self) -> u8 {
+        unsafe { self.data.read() }
+    }
+
+    fn write_mask(&self, mask: u8) {
+        unsafe {
+            self.data.write(mask);
+        }
+    }
+
+    fn read_register(&self, ocw3: u8) -> u8 {
+        unsafe {
+            self.command.write(ocw3);
+            self.command.read()
+        }
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -55,12 +80,6 @@ impl x86PIC {
         let wait_port: Port = Port::new(0x80);
         let wait = || wait_port.write(0);
 
-        // Save our original interrupt masks, because I'm too lazy to
-        // figure out reasonable values.  We'll restore these when we're
-        // done.
-        let saved_mask1 = self.pic1.data.read();
-        let saved_mask2 = self.pic2.data.read();
-
         // Tell each PIC that we're going to send it a three-byte
         // initialization sequence on its data port.
         self.pic1.command.write(CMD_INIT);
@@ -86,20 +105,86 @@ impl x86PIC {
         self.pic2.data.write(MODE_8086);
         wait();
 
-        // Restore our saved masks.
-        self.pic1.data.write(saved_mask1);
-        self.pic2.data.write(saved_mask2);
+        // Every line stays masked until a handler is registered for it,
+        // only the cascade to the slave PIC is needed all the time.
+        self.disable_all();
+        self.unmask(CASCADE_IRQ);
 
         ok!("PICs initialized");
     }
 
-    pub fn send_end_of_interrupt(&self, interrupt: InterruptType) {
+    pub fn mask(&self, irq: u8) {
+        let (pic, line) = self.pic_for(irq);
+        pic.write_mask(pic.read_mask() | (1 << line));
+    }
+
+    pub fn unmask(&self, irq: u8) {
+        let (pic, line) = self.pic_for(irq);
+        pic.write_mask(pic.read_mask() & !(1 << line));
+    }
+
+    pub fn disable_all(&self) {
+        self.pic1.write_mask(0xff);
+        self.pic2.write_mask(0xff);
+    }
+
+    /// Bit n is set if IRQ n is masked.
+    #[allow(dead_code)]
+    pub fn masks(&self) -> u16 {
+        u16::from_le_bytes([self.pic1.read_mask(), self.pic2.read_mask()])
+    }
+
+    /// In-Service Register: bit n is set if IRQ n is currently being handled.
+    pub fn read_isr(&self) -> u16 {
+        u16::from_le_bytes([
+            self.pic1.read_register(CMD_READ_ISR),
+            self.pic2.read_register(CMD_READ_ISR),
+        ])
+    }
+
+    /// Interrupt Request Register: bit n is set if IRQ n has been raised
+    /// but not yet delivered to the CPU.
+    #[allow(dead_code)]
+    pub fn read_irr(&self) -> u16 {
+        u16::from_le_bytes([
+            self.pic1.read_register(CMD_READ_IRR),
+            self.pic2.read_register(CMD_READ_IRR),
+        ])
+    }
+
+    /// A PIC raises IRQ7 (or IRQ15 for the slave) if an interrupt request went
+    /// away before it was acknowledged. Such an interrupt is not in service.
+    pub fn is_spurious(&self, irq: u8) -> bool {
+        match irq {
+            7 | 15 => self.read_isr() & (1 << irq) == 0,
+            _ => false,
+        }
+    }
+
+    /// Finishes a spurious interrupt. The slave PIC must not get an EOI, but
+    /// the master PIC did see a real interrupt on the cascade line.
+    pub fn end_spurious_interrupt(&self, irq: u8) {
+        if irq == 15 {
+            self.pic1.send_end_of_interrupt();
+        }
+    }
+
+    fn pic_for(&self, irq: u8) -> (&PIC, u8) {
+        assert!(irq < 16, "Invalid PIC irq {}", irq);
+        if irq < 8 {
+            (&self.pic1, irq)
+        } else {
+            (&self.pic2, irq - 8)
+        }
+    }
+
+    pub fn send_end_of_interrupt(&self, irq: u8) {
         // Ignore if interrupt is not from pics
-        if interrupt.as_usize() > 15 {
+        if irq > 15 {
             return;
         }
         // Is interrupt from second PIC?
-        if interrupt.as_usize() > 7 {
+        if irq > 7 {
             self.pic2.send_end_of_interrupt();
         }
         self.pic1.send_end_of_interrupt();
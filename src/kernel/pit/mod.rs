@@ -0,0 +1,79 @@
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use core::time::Duration;
+
+use self::pit::{ProgrammableIntervalTimer, BASE_FREQUENCY};
+use crate::interrupts::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::interrupts::irq::{self, IrqReturn};
+use crate::ylib::sync::mutex::Mutex;
+
+pub mod pit;
+
+pub const DEFAULT_FREQUENCY: u32 = 1000;
+
+/// Longest wait of channel 2 in one go, the count register has 16 bits.
+const MAX_WAIT_MS: u64 = 50;
+
+pub static PIT: Mutex<ProgrammableIntervalTimer> = Mutex::new(ProgrammableIntervalTimer::new());
+
+static TICKS: AtomicU64 = AtomicU64::new(0);
+/// Cycles of the base frequency elapsed in all ticks. Each tick adds the
+/// divisor it was counted with, so changing the frequency keeps the uptime.
+static CYCLES: AtomicU64 = AtomicU64::new(0);
+/// Divisor of channel 0, 0 until the PIT is initialized.
+static DIVISOR: AtomicU32 = AtomicU32::new(0);
+
+/// Programs channel 0 to the given frequency and counts its interrupts.
+pub fn init(frequency: u32) {
+    set_frequency(frequency);
+    irq::register_irq(irq::TIMER_IRQ, tick_handler).expect("Could not register PIT handler");
+    ok!(
+        "PIT initialized with {} Hz (requested {} Hz)",
+        self::frequency(),
+        frequency
+    );
+}
+
+/// Changes the tick rate. The frequency is rounded to the nearest divisor
+/// of the 1.193182 MHz base frequency.
+pub fn set_frequency(frequency: u32) {
+    let divisor = ((BASE_FREQUENCY + frequency / 2) / frequency.max(1)).clamp(1, 65536);
+    PIT.lock().set_channel_0_divisor(divisor as u16);
+    DIVISOR.store(divisor, Ordering::SeqCst);
+}
+
+/// The actual frequency of channel 0 in Hz.
+pub fn frequency() -> u32 {
+    match DIVISOR.load(Ordering::SeqCst) {
+        0 => 0,
+        divisor => BASE_FREQUENCY / divisor,
+    }
+}
+
+/// Number of timer interrupts since the PIT was initialized.
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::SeqCst)
+}
+
+/// Time since the PIT was initialized, with the resolution of one tick.
+pub fn uptime() -> Duration {
+    let cycles = CYCLES.load(Ordering::SeqCst) as u128;
+    let nanos = cycles * 1_000_000_000 / BASE_FREQUENCY as u128;
+    Duration::from_nanos(nanos as u64)
+}
+
+/// Busy waits with channel 2, so it also works before interrupts are enabled.
+pub fn sleep_ms(milliseconds: u64) {
+    let mut remaining = milliseconds;
+    while remaining > 0 {
+        let chunk = remaining.min(MAX_WAIT_MS);
+        let count = BASE_FREQUENCY as u64 * chunk / 1000;
+        PIT.lock().wait_channel_2(count as u16);
+        remaining -= chunk;
+    }
+}
+
+fn tick_handler(_stack_frame: &InterruptStackFrame) -> IrqReturn {
+    TICKS.fetch_add(1, Ordering::SeqCst);
+    CYCLES.fetch_add(DIVISOR.load(Ordering::SeqCst) as u64, Ordering::SeqCst);
+    IrqReturn::Handled
+}
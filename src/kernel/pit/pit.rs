@@ -0,0 +1,73 @@
+use crate::asm::Port;
+
+/// Frequency of the oscillator driving all PIT channels.
+pub const BASE_FREQUENCY: u32 = 1_193_182;
+
+const CHANNEL_0_ADDRESS: u16 = 0x40;
+const CHANNEL_2_ADDRESS: u16 = 0x42;
+const COMMAND_ADDRESS: u16 = 0x43;
+/// Gate and output of channel 2 are connected to the PC speaker control port.
+const SPEAKER_CONTROL_ADDRESS: u16 = 0x61;
+
+// Command byte: channel (bits 6-7), access mode (bits 4-5), operating mode (bits 1-3)
+const CMD_CHANNEL_0: u8 = 0b00 << 6;
+const CMD_CHANNEL_2: u8 = 0b10 << 6;
+const CMD_ACCESS_LOW_HIGH: u8 = 0b11 << 4;
+const CMD_MODE_TERMINAL_COUNT: u8 = 0b000 << 1;
+const CMD_MODE_RATE_GENERATOR: u8 = 0b010 << 1;
+
+const SPEAKER_CHANNEL_2_GATE: u8 = 1 << 0;
+const SPEAKER_ENABLE: u8 = 1 << 1;
+const SPEAKER_CHANNEL_2_OUTPUT: u8 = 1 << 5;
+
+/// The 8253/8254 Programmable Interval Timer.
+pub struct ProgrammableIntervalTimer {
+    channel_0: Port,
+    channel_2: Port,
+    command: Port,
+    speaker_control: Port,
+}
+
+impl ProgrammableIntervalTimer {
+    pub const fn new() -> Self {
+        ProgrammableIntervalTimer {
+            channel_0: Port::new(CHANNEL_0_ADDRESS),
+            channel_2: Port::new(CHANNEL_2_ADDRESS),
+            command: Port::new(COMMAND_ADDRESS),
+            speaker_control: Port::new(SPEAKER_CONTROL_ADDRESS),
+        }
+    }
+
+    /// Lets channel 0 raise IRQ0 every `divisor` oscillations. A divisor of 0 means 65536.
+    pub fn set_channel_0_divisor(&mut self, divisor: u16) {
+        unsafe {
+            self.command
+                .write(CMD_CHANNEL_0 | CMD_ACCESS_LOW_HIGH | CMD_MODE_RATE_GENERATOR);
+            self.channel_0.write(divisor as u8);
+            self.channel_0.write((divisor >> 8) as u8);
+        }
+    }
+
+    /// Busy waits until channel 2 counted down from `count`. This works
+    /// without interrupts, because the output of channel 2 can be polled.
+    pub fn wait_channel_2(&mut self, count: u16) {
+        unsafe {
+            // Disable the speaker and stop the counter while it is programmed
+            let control = self.speaker_control.read() & !(SPEAKER_ENABLE | SPEAKER_CHANNEL_2_GATE);
+            self.speaker_control.write(control);
+
+            self.command
+                .write(CMD_CHANNEL_2 | CMD_ACCESS_LOW_HIGH | CMD_MODE_TERMINAL_COUNT);
+            self.channel_2.write(count as u8);
+            self.channel_2.write((count >> 8) as u8);
+
+            // A rising gate starts the count, the output goes high at zero
+            self.speaker_control.write(control | SPEAKER_CHANNEL_2_GATE);
+            while self.speaker_control.read() & SPEAKER_CHANNEL_2_OUTPUT == 0 {
+                core::hint::spin_loop();
+            }
+
+            self.speaker_control.write(control);
+        }
+    }
+}
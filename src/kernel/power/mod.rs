@@ -0,0 +1,254 @@
+pub mod s5;
+
+use self::s5::SleepType;
+use crate::acpi::generic_address::{AddressSpace, GenericAddress};
+use crate::acpi::sdt::Sdt;
+use crate::asm::Port;
+use crate::memory::mmio;
+use crate::memory::phys_addr::PhysAddr;
+use crate::memory::virt_addr::VirtAddr;
+use crate::memory::DescriptorTablePointer;
+use crate::multiboot::command_line;
+use crate::ylib::sync::mutex::Mutex;
+
+/// PM1 control register bits.
+const SCI_ENABLE: u16 = 1 << 0;
+const SLEEP_TYPE_SHIFT: u16 = 10;
+const SLEEP_ENABLE: u16 = 1 << 13;
+
+const KEYBOARD_CONTROLLER_STATUS: u16 = 0x64;
+const KEYBOARD_CONTROLLER_INPUT_FULL: u8 = 1 << 1;
+const KEYBOARD_CONTROLLER_RESET: u8 = 0xfe;
+
+/// Number of polls before giving up on a hardware state change.
+const POLL_ITERATIONS: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerError {
+    /// The FADT or the `\_S5` object of the DSDT is missing.
+    ShutdownNotSupported,
+    /// ACPI mode could not be enabled through the SMI command port.
+    AcpiEnableFailed,
+    /// The machine is still running after entering S5.
+    ShutdownFailed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicAction {
+    Halt,
+    Reboot,
+    Shutdown,
+}
+
+/// The ACPI reset register. A memory mapped one is mapped at boot, so a reboot
+/// from the panic handler does not need the page table or frame allocator.
+#[derive(Debug, Clone, Copy)]
+enum ResetRegister {
+    Port(u16),
+    Memory(VirtAddr),
+}
+
+/// The registers needed for a shutdown or reboot, taken from the ACPI tables
+/// at boot, so the panic handler does not need to parse them.
+#[derive(Debug, Clone, Copy)]
+struct AcpiPower {
+    smi_command_port: u32,
+    acpi_enable: u8,
+    pm1a_control_block: u32,
+    pm1b_control_block: u32,
+    sleep_type: Option<SleepType>,
+    reset_register: Option<ResetRegister>,
+    reset_value: u8,
+}
+
+static ACPI_POWER: Mutex<Option<AcpiPower>> = Mutex::new(None);
+static PANIC_ACTION: Mutex<PanicAction> = Mutex::new(PanicAction::Halt);
+
+/// Reads the power management registers from the ACPI tables and the panic
+/// action from the kernel command line (`panic=halt|reboot|shutdown`).
+pub fn init(command_line: Option<&str>) {
+    if let Some(action) = command_line.and_then(parse_panic_action) {
+        *PANIC_ACTION.lock() = action;
+    }
+
+    let fadt = match crate::acpi::tables().and_then(|tables| tables.fadt) {
+        Some(fadt) => fadt,
+        None => {
+            ok!("No FADT found, ACPI shutdown is not available");
+            return;
+        }
+    };
+
+    let sleep_type = if fadt.dsdt_address == 0 {
+        serial_println!("The FADT has no DSDT");
+        None
+    } else {
+        match unsafe { Sdt::load(fadt.dsdt_address) } {
+            Ok(dsdt) => s5::find(dsdt.bytes()),
+            Err(error) => {
+                serial_println!("Could not load the DSDT: {:?}", error);
+                None
+            }
+        }
+    };
+
+    let acpi_power = AcpiPower {
+        smi_command_port: fadt.smi_command_port,
+        acpi_enable: fadt.acpi_enable,
+        pm1a_control_block: fadt.pm1a_control_block,
+        pm1b_control_block: fadt.pm1b_control_block,
+        sleep_type,
+        reset_register: fadt.reset_register.as_ref().and_then(map_reset_register),
+        reset_value: fadt.reset_value,
+    };
+    *ACPI_POWER.lock() = Some(acpi_power);
+
+    ok!(
+        "Power management initialized (S5 {:?}, reset register {}, panic action {:?})",
+        sleep_type,
+        if acpi_power.reset_register.is_some() {
+            "available"
+        } else {
+            "not available"
+        },
+        *PANIC_ACTION.lock()
+    );
+}
+
+pub fn panic_action() -> PanicAction {
+    *PANIC_ACTION.lock()
+}
+
+/// Turns the machine off by entering the ACPI sleep state S5. Only returns
+/// if that fails.
+pub fn shutdown() -> PowerError {
+    crate::asm::interrupts::disable_interrupts();
+
+    if let Err(error) = enter_s5() {
+        return error;
+    }
+    wait();
+    PowerError::ShutdownFailed
+}
+
+/// Resets the machine with the ACPI reset register, the 8042 keyboard
+/// controller or, if all else fails, a triple fault.
+pub fn reboot() -> ! {
+    crate::asm::interrupts::disable_interrupts();
+
+    let acpi_power = *ACPI_POWER.lock();
+    if let Some(AcpiPower {
+        reset_register: Some(reset_register),
+        reset_value,
+        ..
+    }) = acpi_power
+    {
+        unsafe { write_reset_register(reset_register, reset_value) };
+        wait();
+    }
+
+    unsafe {
+        let status = Port::new(KEYBOARD_CONTROLLER_STATUS);
+        for _ in 0..POLL_ITERATIONS {
+            if status.read() & KEYBOARD_CONTROLLER_INPUT_FULL == 0 {
+                break;
+            }
+        }
+        status.write(KEYBOARD_CONTROLLER_RESET);
+    }
+    wait();
+
+    triple_fault()
+}
+
+fn enter_s5() -> Result<(), PowerError> {
+    let acpi_power = (*ACPI_POWER.lock()).ok_or(PowerError::ShutdownNotSupported)?;
+    let sleep_type = acpi_power
+        .sleep_type
+        .ok_or(PowerError::ShutdownNotSupported)?;
+    if acpi_power.pm1a_control_block == 0 {
+        return Err(PowerError::ShutdownNotSupported);
+    }
+
+    let pm1a_control = Port::new(acpi_power.pm1a_control_block as u16);
+    unsafe {
+        enable_acpi_mode(&acpi_power, &pm1a_control)?;
+
+        pm1a_control.write_u16((sleep_type.pm1a << SLEEP_TYPE_SHIFT) | SLEEP_ENABLE);
+        if acpi_power.pm1b_control_block != 0 {
+            let pm1b_control = Port::new(acpi_power.pm1b_control_block as u16);
+            pm1b_control.write_u16((sleep_type.pm1b << SLEEP_TYPE_SHIFT) | SLEEP_ENABLE);
+        }
+    }
+    Ok(())
+}
+
+/// Firmware which starts in legacy mode switches to ACPI mode once the
+/// ACPI enable value is written to the SMI command port.
+unsafe fn enable_acpi_mode(acpi_power: &AcpiPower, pm1a_control: &Port) -> Result<(), PowerError> {
+    if pm1a_control.read_u16() & SCI_ENABLE != 0
+        || acpi_power.smi_command_port == 0
+        || acpi_power.acpi_enable == 0
+    {
+        return Ok(());
+    }
+
+    Port::new(acpi_power.smi_command_port as u16).write(acpi_power.acpi_enable);
+    for _ in 0..POLL_ITERATIONS {
+        if pm1a_control.read_u16() & SCI_ENABLE != 0 {
+            return Ok(());
+        }
+    }
+    Err(PowerError::AcpiEnableFailed)
+}
+
+fn map_reset_register(reset_register: &GenericAddress) -> Option<ResetRegister> {
+    match reset_register.address_space {
+        AddressSpace::SystemIo => Some(ResetRegister::Port(reset_register.address as u16)),
+        AddressSpace::SystemMemory => {
+            let address = PhysAddr::try_new(reset_register.address).ok()?;
+            match mmio::identity_map(address, 1) {
+                Ok(address) => Some(ResetRegister::Memory(address)),
+                Err(error) => {
+                    serial_println!("Could not map the reset register: {:?}", error);
+                    None
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+unsafe fn write_reset_register(reset_register: ResetRegister, value: u8) {
+    match reset_register {
+        ResetRegister::Port(port) => Port::new(port).write(value),
+        ResetRegister::Memory(address) => core::ptr::write_volatile(address.as_mut_ptr(), value),
+    }
+}
+
+/// Loads an empty IDT, so the next interrupt causes a triple fault.
+fn triple_fault() -> ! {
+    let pointer = DescriptorTablePointer {
+        limit: 0,
+        base: VirtAddr::new(0),
+    };
+    unsafe {
+        asm!("lidt [{}]", "int3", in(reg) &pointer, options(nostack));
+    }
+    crate::asm::halt::halt_loop()
+}
+
+fn wait() {
+    for _ in 0..POLL_ITERATIONS {
+        core::hint::spin_loop();
+    }
+}
+
+fn parse_panic_action(command_line: &str) -> Option<PanicAction> {
+    match command_line::argument(command_line, "panic")? {
+        "halt" => Some(PanicAction::Halt),
+        "reboot" => Some(PanicAction::Reboot),
+        "shutdown" => Some(PanicAction::Shutdown),
+        _ => None,
+    }
+}
@@ -0,0 +1,57 @@
+/// AML opcodes needed to decode the `\_S5` package.
+const NAME_OP: u8 = 0x08;
+const PACKAGE_OP: u8 = 0x12;
+const ZERO_OP: u8 = 0x00;
+const ONE_OP: u8 = 0x01;
+const BYTE_PREFIX: u8 = 0x0a;
+
+/// Sleep types of the S5 (soft off) state for the PM1a and PM1b control blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SleepType {
+    pub pm1a: u16,
+    pub pm1b: u16,
+}
+
+/// Searches the AML of the DSDT for `Name(\_S5, Package() { a, b, ... })`.
+///
+/// This is not a full AML interpreter and only understands a package of
+/// constant integers, which is what firmware uses in practice.
+pub fn find(aml: &[u8]) -> Option<SleepType> {
+    let position = aml.windows(4).position(|name| name == b"_S5_")?;
+
+    // The name is preceded by NameOp, optionally with a root prefix
+    let is_name = match position {
+        0 => false,
+        1 => aml[0] == NAME_OP,
+        _ => {
+            aml[position - 1] == NAME_OP
+                || (aml[position - 1] == b'\\' && aml[position - 2] == NAME_OP)
+        }
+    };
+    if !is_name {
+        return None;
+    }
+
+    let mut bytes = aml[position + 4..].iter().copied();
+    if bytes.next()? != PACKAGE_OP {
+        return None;
+    }
+
+    // Bits 6 and 7 of the package length encode the number of following length bytes
+    let package_length = bytes.next()?;
+    for _ in 0..(package_length >> 6) {
+        bytes.next()?;
+    }
+    let _element_count = bytes.next()?;
+
+    let mut integer = || match bytes.next()? {
+        ZERO_OP => Some(0),
+        ONE_OP => Some(1),
+        BYTE_PREFIX => bytes.next().map(|value| value as u16),
+        _ => None,
+    };
+
+    let pm1a = integer()?;
+    let pm1b = integer()?;
+    Some(SleepType { pm1a, pm1b })
+}
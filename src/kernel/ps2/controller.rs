@@ -0,0 +1,172 @@
+use crate::asm::Port;
+
+const DATA_PORT: u16 = 0x60;
+/// Reads return the status, writes send a controller command.
+const STATUS_COMMAND_PORT: u16 = 0x64;
+
+const STATUS_OUTPUT_FULL: u8 = 1 << 0;
+const STATUS_INPUT_FULL: u8 = 1 << 1;
+/// The byte in the output buffer is from the second port.
+const STATUS_SECOND_PORT_DATA: u8 = 1 << 5;
+
+/// Number of status polls before giving up on the controller or a device.
+const POLL_ITERATIONS: usize = 1_000_000;
+/// Number of status polls for bytes which a device might not send at all.
+const OPTIONAL_POLL_ITERATIONS: usize = 100_000;
+
+pub const COMMAND_READ_CONFIG: u8 = 0x20;
+pub const COMMAND_WRITE_CONFIG: u8 = 0x60;
+pub const COMMAND_DISABLE_SECOND_PORT: u8 = 0xa7;
+pub const COMMAND_ENABLE_SECOND_PORT: u8 = 0xa8;
+pub const COMMAND_TEST_SECOND_PORT: u8 = 0xa9;
+pub const COMMAND_SELF_TEST: u8 = 0xaa;
+pub const COMMAND_TEST_FIRST_PORT: u8 = 0xab;
+pub const COMMAND_DISABLE_FIRST_PORT: u8 = 0xad;
+pub const COMMAND_ENABLE_FIRST_PORT: u8 = 0xae;
+/// The next byte written to the data port goes to the second port.
+pub const COMMAND_WRITE_SECOND_PORT: u8 = 0xd4;
+
+pub const SELF_TEST_PASSED: u8 = 0x55;
+pub const PORT_TEST_PASSED: u8 = 0x00;
+
+// Configuration byte
+pub const CONFIG_FIRST_PORT_INTERRUPT: u8 = 1 << 0;
+pub const CONFIG_SECOND_PORT_INTERRUPT: u8 = 1 << 1;
+pub const CONFIG_SECOND_PORT_CLOCK_DISABLED: u8 = 1 << 5;
+/// The controller translates scancode set 2 of the first port to set 1.
+pub const CONFIG_FIRST_PORT_TRANSLATION: u8 = 1 << 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ps2Port {
+    First,
+    Second,
+}
+
+/// The controller did not get ready within the polling limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout;
+
+/// The 8042 PS/2 controller.
+pub struct Controller {
+    data: Port,
+    status_command: Port,
+}
+
+impl Controller {
+    pub const fn new() -> Self {
+        Controller {
+            data: Port::new(DATA_PORT),
+            status_command: Port::new(STATUS_COMMAND_PORT),
+        }
+    }
+
+    pub fn command(&self, command: u8) -> Result<(), Timeout> {
+        self.wait_input_empty()?;
+        unsafe { self.status_command.write(command) };
+        Ok(())
+    }
+
+    /// Sends a controller command which answers with one byte.
+    pub fn command_with_response(&self, command: u8) -> Result<u8, Timeout> {
+        self.command(command)?;
+        self.read_data()
+    }
+
+    pub fn read_config(&self) -> Result<u8, Timeout> {
+        self.command_with_response(COMMAND_READ_CONFIG)
+    }
+
+    pub fn write_config(&self, config: u8) -> Result<(), Timeout> {
+        self.command(COMMAND_WRITE_CONFIG)?;
+        self.write_data(config)
+    }
+
+    /// Enables or disables the interrupt of a port in the configuration byte.
+    pub fn set_interrupt(&self, port: Ps2Port, enabled: bool) -> Result<(), Timeout> {
+        let bit = match port {
+            Ps2Port::First => CONFIG_FIRST_PORT_INTERRUPT,
+            Ps2Port::Second => CONFIG_SECOND_PORT_INTERRUPT,
+        };
+        let config = self.read_config()?;
+        self.write_config(if enabled { config | bit } else { config & !bit })
+    }
+
+    /// Sends a byte to the device of a port.
+    pub fn write_device(&self, port: Ps2Port, byte: u8) -> Result<(), Timeout> {
+        if port == Ps2Port::Second {
+            self.command(COMMAND_WRITE_SECOND_PORT)?;
+        }
+        self.write_data(byte)
+    }
+
+    pub fn write_data(&self, byte: u8) -> Result<(), Timeout> {
+        self.wait_input_empty()?;
+        unsafe { self.data.write(byte) };
+        Ok(())
+    }
+
+    pub fn read_data(&self) -> Result<u8, Timeout> {
+        self.wait_output_full(POLL_ITERATIONS)?;
+        Ok(unsafe { self.data.read() })
+    }
+
+    /// Reads a byte with a shorter timeout.
+    pub fn read_optional_data(&self) -> Option<u8> {
+        self.wait_output_full(OPTIONAL_POLL_ITERATIONS).ok()?;
+        Some(unsafe { self.data.read() })
+    }
+
+    /// Reads the byte behind an interrupt of the port without waiting. Returns
+    /// `None` if there is no byte or if it belongs to the other port.
+    pub fn read_interrupt_data(&self, port: Ps2Port) -> Option<u8> {
+        let status = self.status();
+        let is_second_port = status & STATUS_SECOND_PORT_DATA != 0;
+        if status & STATUS_OUTPUT_FULL == 0 || is_second_port != (port == Ps2Port::Second) {
+            return None;
+        }
+        Some(unsafe { self.data.read() })
+    }
+
+    /// Returns a byte of the first port without waiting, for polling while
+    /// interrupts are disabled. Bytes of the second port are dropped.
+    pub fn poll_first_port(&self) -> Option<u8> {
+        let status = self.status();
+        if status & STATUS_OUTPUT_FULL == 0 {
+            return None;
+        }
+        let byte = unsafe { self.data.read() };
+        if status & STATUS_SECOND_PORT_DATA != 0 {
+            return None;
+        }
+        Some(byte)
+    }
+
+    /// Discards bytes which were received before the controller was set up.
+    pub fn flush(&self) {
+        while self.status() & STATUS_OUTPUT_FULL != 0 {
+            unsafe { self.data.read() };
+        }
+    }
+
+    fn status(&self) -> u8 {
+        unsafe { self.status_command.read() }
+    }
+
+    fn wait_input_empty(&self) -> Result<(), Timeout> {
+        for _ in 0..POLL_ITERATIONS {
+            if self.status() & STATUS_INPUT_FULL == 0 {
+                return Ok(());
+            }
+        }
+        Err(Timeout)
+    }
+
+    fn wait_output_full(&self, iterations: usize) -> Result<(), Timeout> {
+        for _ in 0..iterations {
+            if self.status() & STATUS_OUTPUT_FULL != 0 {
+                return Ok(());
+            }
+        }
+        Err(Timeout)
+    }
+}
@@ -0,0 +1,127 @@
+use super::controller::{Controller, Ps2Port, Timeout};
+
+pub const ACK: u8 = 0xfa;
+pub const RESEND: u8 = 0xfe;
+const SELF_TEST_PASSED: u8 = 0xaa;
+
+pub const COMMAND_SET_LEDS: u8 = 0xed;
+pub const COMMAND_SCANCODE_SET: u8 = 0xf0;
+const COMMAND_IDENTIFY: u8 = 0xf2;
+pub const COMMAND_SET_TYPEMATIC: u8 = 0xf3;
+/// Same command as the typematic rate of keyboards.
+pub const COMMAND_SET_SAMPLE_RATE: u8 = 0xf3;
+pub const COMMAND_ENABLE_SCANNING: u8 = 0xf4;
+pub const COMMAND_DISABLE_SCANNING: u8 = 0xf5;
+const COMMAND_RESET: u8 = 0xff;
+
+/// How often a byte is sent again when the device asks for it.
+pub const MAX_RETRIES: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceError {
+    Timeout,
+    /// The device answered with something else than ACK.
+    NoAck(u8),
+    /// The device answered to a reset with something else than 0xAA.
+    SelfTestFailed(u8),
+}
+
+impl From<Timeout> for DeviceError {
+    fn from(_: Timeout) -> Self {
+        DeviceError::Timeout
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceType {
+    /// Old AT keyboards do not send an identification.
+    AtKeyboard,
+    Mf2Keyboard,
+    Mouse,
+    /// Mouse with a scroll wheel.
+    IntelliMouse,
+    /// Mouse with a scroll wheel and five buttons.
+    IntelliMouseExplorer,
+    Unknown,
+}
+
+impl DeviceType {
+    fn from_identification(bytes: &[u8]) -> Self {
+        match bytes {
+            [] => DeviceType::AtKeyboard,
+            // The second byte is 0x41 or 0xc1 if the controller translates it
+            [0xab, 0x41] | [0xab, 0x83] | [0xab, 0xc1] => DeviceType::Mf2Keyboard,
+            [0x00] => DeviceType::Mouse,
+            [0x03] => DeviceType::IntelliMouse,
+            [0x04] => DeviceType::IntelliMouseExplorer,
+            _ => DeviceType::Unknown,
+        }
+    }
+
+    pub fn is_keyboard(&self) -> bool {
+        matches!(self, DeviceType::AtKeyboard | DeviceType::Mf2Keyboard)
+    }
+
+    pub fn is_mouse(&self) -> bool {
+        matches!(
+            self,
+            DeviceType::Mouse | DeviceType::IntelliMouse | DeviceType::IntelliMouseExplorer
+        )
+    }
+}
+
+/// Sends a command byte to a device and waits for the ACK. These polled
+/// commands are only used while the interrupts of the controller are disabled.
+pub fn command(controller: &Controller, port: Ps2Port, byte: u8) -> Result<(), DeviceError> {
+    for _ in 0..=MAX_RETRIES {
+        controller.write_device(port, byte)?;
+        match controller.read_data()? {
+            ACK => return Ok(()),
+            RESEND => continue,
+            response => return Err(DeviceError::NoAck(response)),
+        }
+    }
+    Err(DeviceError::NoAck(RESEND))
+}
+
+/// Resets the device and waits for its self test.
+pub fn reset(controller: &Controller, port: Ps2Port) -> Result<(), DeviceError> {
+    command(controller, port, COMMAND_RESET)?;
+    match controller.read_data()? {
+        SELF_TEST_PASSED => {}
+        response => return Err(DeviceError::SelfTestFailed(response)),
+    }
+    // Mice send their ID after the self test
+    controller.read_optional_data();
+    Ok(())
+}
+
+/// Sends a command with one data byte.
+pub fn command_with_data(
+    controller: &Controller,
+    port: Ps2Port,
+    byte: u8,
+    data: u8,
+) -> Result<(), DeviceError> {
+    command(controller, port, byte)?;
+    command(controller, port, data)
+}
+
+/// Scanning has to be disabled during the identification, it stays disabled.
+pub fn identify(controller: &Controller, port: Ps2Port) -> Result<DeviceType, DeviceError> {
+    command(controller, port, COMMAND_DISABLE_SCANNING)?;
+    command(controller, port, COMMAND_IDENTIFY)?;
+
+    let mut bytes = [0; 2];
+    let mut length = 0;
+    while length < bytes.len() {
+        match controller.read_optional_data() {
+            Some(byte) => {
+                bytes[length] = byte;
+                length += 1;
+            }
+            None => break,
+        }
+    }
+    Ok(DeviceType::from_identification(&bytes[..length]))
+}
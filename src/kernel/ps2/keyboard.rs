@@ -0,0 +1,154 @@
+use super::controller::{Controller, Ps2Port};
+use super::device::{ACK, COMMAND_SET_LEDS, COMMAND_SET_TYPEMATIC, MAX_RETRIES, RESEND};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Leds {
+    pub scroll_lock: bool,
+    pub num_lock: bool,
+    pub caps_lock: bool,
+}
+
+impl Leds {
+    pub fn to_byte(self) -> u8 {
+        (self.scroll_lock as u8) | (self.num_lock as u8) << 1 | (self.caps_lock as u8) << 2
+    }
+}
+
+/// Key repeat settings of the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Typematic(u8);
+
+impl Typematic {
+    /// 10.9 repeats per second after 500 ms, like most BIOSes.
+    pub const DEFAULT: Typematic = Typematic(0x2b);
+
+    /// Rounds to the nearest supported values, 2 to 30 repeats per second and
+    /// 250 to 1000 ms delay in steps of 250 ms.
+    #[allow(dead_code)]
+    pub fn new(repeats_per_second: u32, delay_ms: u32) -> Self {
+        let target_period_us = 1_000_000 / repeats_per_second.clamp(2, 30);
+        // The period is (8 + bits 0-2) * 2 ^ bits 3-4 * 4.17 ms
+        let period_us = |rate: u32| (8 + (rate & 0b111)) * (1 << (rate >> 3)) * 4170;
+        let rate = (0..32)
+            .min_by_key(|&rate| (period_us(rate) as i64 - target_period_us as i64).abs())
+            .unwrap_or(0);
+
+        let delay = ((delay_ms.clamp(250, 1000) + 125) / 250 - 1).min(3);
+        Typematic((delay << 5 | rate) as u8)
+    }
+
+    pub fn to_byte(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct InFlight {
+    bytes: [u8; 2],
+    /// Index of the byte waiting for its ACK.
+    index: usize,
+    retries: u8,
+}
+
+/// Sends the LED and typematic commands to the keyboard while its interrupt
+/// is enabled. The responses arrive as bytes of IRQ1 and have to be passed
+/// to `handle_response`. Only the latest requested state of each setting is
+/// sent, so fast toggling of the lock keys does not pile up commands.
+pub struct CommandQueue {
+    in_flight: Option<InFlight>,
+    leds: Option<Leds>,
+    typematic: Option<Typematic>,
+    failed_commands: u64,
+}
+
+impl CommandQueue {
+    pub const fn new() -> Self {
+        CommandQueue {
+            in_flight: None,
+            leds: None,
+            typematic: None,
+            failed_commands: 0,
+        }
+    }
+
+    pub fn set_leds(&mut self, controller: &Controller, leds: Leds) {
+        self.leds = Some(leds);
+        self.send_next(controller);
+    }
+
+    pub fn set_typematic(&mut self, controller: &Controller, typematic: Typematic) {
+        self.typematic = Some(typematic);
+        self.send_next(controller);
+    }
+
+    /// Returns false if the byte is not a response but a scancode.
+    pub fn handle_response(&mut self, controller: &Controller, byte: u8) -> bool {
+        let in_flight = match self.in_flight.as_mut() {
+            Some(in_flight) => in_flight,
+            None => return byte == ACK || byte == RESEND,
+        };
+
+        match byte {
+            ACK => {
+                in_flight.index += 1;
+                in_flight.retries = 0;
+                if in_flight.index < in_flight.bytes.len() {
+                    let next = in_flight.bytes[in_flight.index];
+                    self.write(controller, next);
+                } else {
+                    self.in_flight = None;
+                    self.send_next(controller);
+                }
+                true
+            }
+            RESEND if in_flight.retries < MAX_RETRIES => {
+                in_flight.retries += 1;
+                let byte = in_flight.bytes[in_flight.index];
+                self.write(controller, byte);
+                true
+            }
+            RESEND => {
+                self.fail(controller);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn failed_commands(&self) -> u64 {
+        self.failed_commands
+    }
+
+    fn send_next(&mut self, controller: &Controller) {
+        if self.in_flight.is_some() {
+            return;
+        }
+
+        let bytes = if let Some(leds) = self.leds.take() {
+            [COMMAND_SET_LEDS, leds.to_byte()]
+        } else if let Some(typematic) = self.typematic.take() {
+            [COMMAND_SET_TYPEMATIC, typematic.to_byte()]
+        } else {
+            return;
+        };
+        self.in_flight = Some(InFlight {
+            bytes,
+            index: 0,
+            retries: 0,
+        });
+        self.write(controller, bytes[0]);
+    }
+
+    fn write(&mut self, controller: &Controller, byte: u8) {
+        if controller.write_device(Ps2Port::First, byte).is_err() {
+            self.fail(controller);
+        }
+    }
+
+    /// Drops the current command and continues with the next one.
+    fn fail(&mut self, controller: &Controller) {
+        self.failed_commands += 1;
+        self.in_flight = None;
+        self.send_next(controller);
+    }
+}
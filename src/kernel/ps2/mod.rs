@@ -0,0 +1,231 @@
+use self::controller::*;
+use self::device::{DeviceError, DeviceType};
+use self::keyboard::{CommandQueue, Leds, Typematic};
+use crate::ylib::sync::mutex::Mutex;
+
+pub mod controller;
+pub mod device;
+pub mod keyboard;
+
+/// Scancode set 2, which the controller translates to the set 1 of the decoder.
+const SCANCODE_SET: u8 = 2;
+
+pub static CONTROLLER: Controller = Controller::new();
+
+static DEVICES: Mutex<[Option<DeviceType>; 2]> = Mutex::new([None; 2]);
+static KEYBOARD_COMMANDS: Mutex<CommandQueue> = Mutex::new(CommandQueue::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ps2Error {
+    /// The ACPI FADT reports that there is no 8042 controller.
+    NotPresent,
+    Timeout,
+    SelfTestFailed(u8),
+    PortTestFailed(Ps2Port, u8),
+    Device(Ps2Port, DeviceError),
+}
+
+impl From<Timeout> for Ps2Error {
+    fn from(_: Timeout) -> Self {
+        Ps2Error::Timeout
+    }
+}
+
+/// Tests the controller and both ports, resets and identifies the devices and
+/// configures the keyboard. Has to run before the interrupts are enabled, all
+/// device responses are polled.
+pub fn init() -> Result<(), Ps2Error> {
+    let fadt = crate::acpi::tables().and_then(|tables| tables.fadt);
+    if fadt.is_some_and(|fadt| !fadt.has_8042()) {
+        return Err(Ps2Error::NotPresent);
+    }
+
+    let controller = &CONTROLLER;
+    controller.command(COMMAND_DISABLE_FIRST_PORT)?;
+    controller.command(COMMAND_DISABLE_SECOND_PORT)?;
+    controller.flush();
+
+    let mut config = controller.read_config()?;
+    config &= !(CONFIG_FIRST_PORT_INTERRUPT
+        | CONFIG_SECOND_PORT_INTERRUPT
+        | CONFIG_FIRST_PORT_TRANSLATION);
+    controller.write_config(config)?;
+
+    match controller.command_with_response(COMMAND_SELF_TEST)? {
+        SELF_TEST_PASSED => {}
+        response => return Err(Ps2Error::SelfTestFailed(response)),
+    }
+    // Some controllers reset the configuration during the self test
+    controller.write_config(config)?;
+
+    // The clock of the second port is only enabled if there is one
+    let mut dual_channel = false;
+    if config & CONFIG_SECOND_PORT_CLOCK_DISABLED != 0 {
+        controller.command(COMMAND_ENABLE_SECOND_PORT)?;
+        dual_channel = controller.read_config()? & CONFIG_SECOND_PORT_CLOCK_DISABLED == 0;
+        controller.command(COMMAND_DISABLE_SECOND_PORT)?;
+    }
+
+    test_port(Ps2Port::First)?;
+    if dual_channel {
+        test_port(Ps2Port::Second)?;
+    }
+
+    controller.command(COMMAND_ENABLE_FIRST_PORT)?;
+    if dual_channel {
+        controller.command(COMMAND_ENABLE_SECOND_PORT)?;
+    }
+
+    let first = init_device(Ps2Port::First);
+    let second = if dual_channel {
+        init_device(Ps2Port::Second)
+    } else {
+        None
+    };
+    *DEVICES.lock() = [first, second];
+
+    if first.is_some_and(|device| device.is_keyboard()) {
+        config |= CONFIG_FIRST_PORT_INTERRUPT | CONFIG_FIRST_PORT_TRANSLATION;
+    }
+    controller.write_config(config)?;
+
+    ok!(
+        "PS/2 controller initialized with {} port(s): {:?}, {:?}",
+        if dual_channel { 2 } else { 1 },
+        first,
+        second
+    );
+    Ok(())
+}
+
+pub fn device(port: Ps2Port) -> Option<DeviceType> {
+    let devices = *DEVICES.lock();
+    match port {
+        Ps2Port::First => devices[0],
+        Ps2Port::Second => devices[1],
+    }
+}
+
+/// Updates the type after a driver switched the device to another mode.
+pub fn set_device(port: Ps2Port, device_type: DeviceType) {
+    let mut devices = DEVICES.lock();
+    match port {
+        Ps2Port::First => devices[0] = Some(device_type),
+        Ps2Port::Second => devices[1] = Some(device_type),
+    }
+}
+
+/// Configures the second port by polling. The first port is disabled in the
+/// meantime, so keyboard bytes do not mix with the responses.
+pub fn with_first_port_disabled<F, R>(func: F) -> Result<R, Ps2Error>
+where
+    F: FnOnce(&Controller) -> Result<R, Ps2Error>,
+{
+    CONTROLLER.command(COMMAND_DISABLE_FIRST_PORT)?;
+    CONTROLLER.flush();
+    let result = func(&CONTROLLER);
+    CONTROLLER.command(COMMAND_ENABLE_FIRST_PORT)?;
+    result
+}
+
+pub fn has_keyboard() -> bool {
+    device(Ps2Port::First).is_some_and(|device| device.is_keyboard())
+}
+
+pub fn has_mouse() -> bool {
+    device(Ps2Port::Second).is_some_and(|device| device.is_mouse())
+}
+
+/// Reads the byte of a keyboard interrupt. Returns `None` if there is none or
+/// if it was the response to a keyboard command.
+pub fn read_keyboard_byte() -> Option<u8> {
+    let byte = CONTROLLER.read_interrupt_data(Ps2Port::First)?;
+    let response = crate::interrupts::disable_interrupts_for(|| {
+        KEYBOARD_COMMANDS.lock().handle_response(&CONTROLLER, byte)
+    });
+    if response {
+        None
+    } else {
+        Some(byte)
+    }
+}
+
+pub fn set_leds(leds: Leds) {
+    crate::interrupts::disable_interrupts_for(|| {
+        KEYBOARD_COMMANDS.lock().set_leds(&CONTROLLER, leds)
+    });
+}
+
+#[allow(dead_code)]
+pub fn set_typematic(typematic: Typematic) {
+    crate::interrupts::disable_interrupts_for(|| {
+        KEYBOARD_COMMANDS
+            .lock()
+            .set_typematic(&CONTROLLER, typematic)
+    });
+}
+
+/// Number of keyboard commands which were dropped after too many retries.
+#[allow(dead_code)]
+pub fn failed_keyboard_commands() -> u64 {
+    crate::interrupts::disable_interrupts_for(|| KEYBOARD_COMMANDS.lock().failed_commands())
+}
+
+fn test_port(port: Ps2Port) -> Result<(), Ps2Error> {
+    let command = match port {
+        Ps2Port::First => COMMAND_TEST_FIRST_PORT,
+        Ps2Port::Second => COMMAND_TEST_SECOND_PORT,
+    };
+    match CONTROLLER.command_with_response(command)? {
+        PORT_TEST_PASSED => Ok(()),
+        response => Err(Ps2Error::PortTestFailed(port, response)),
+    }
+}
+
+/// Returns the type of the device, or `None` if there is no working device.
+fn init_device(port: Ps2Port) -> Option<DeviceType> {
+    let result = device::reset(&CONTROLLER, port)
+        .and_then(|_| device::identify(&CONTROLLER, port))
+        .and_then(|device_type| {
+            if device_type.is_keyboard() && port == Ps2Port::First {
+                init_keyboard()?;
+            }
+            Ok(device_type)
+        });
+
+    match result {
+        Ok(device_type) => Some(device_type),
+        Err(error) => {
+            serial_println!(
+                "PS/2 device initialization failed: {:?}",
+                Ps2Error::Device(port, error)
+            );
+            None
+        }
+    }
+}
+
+/// Selects the scancode set, turns off the LEDs, sets the default key repeat
+/// and enables scanning.
+fn init_keyboard() -> Result<(), DeviceError> {
+    let port = Ps2Port::First;
+    device::command_with_data(
+        &CONTROLLER,
+        port,
+        device::COMMAND_SCANCODE_SET,
+        SCANCODE_SET,
+    )?;
+    device::command_with_data(
+        &CONTROLLER,
+        port,
+        device::COMMAND_SET_LEDS,
+        Leds::default().to_byte(),
+    )?;
+    device::command_with_data(
+        &CONTROLLER,
+        port,
+        device::COMMAND_SET_TYPEMATIC,
+        Typematic::DEFAULT.to_byte(),
+    )?;
+    device::command(&CONTROLLER, port, device::COMMAND_ENABLE_SCANNING)
+}
@@ -0,0 +1,74 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use self::date_time::DateTime;
+use self::rtc::Rtc;
+use crate::clock::{self, TickSource};
+use crate::interrupts::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::interrupts::irq::{self, IrqError, IrqReturn};
+use crate::ylib::sync::mutex::Mutex;
+
+pub mod date_time;
+pub mod rtc;
+
+pub static RTC: Mutex<Rtc> = Mutex::new(Rtc::new());
+
+static PERIODIC_INTERRUPTS: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtcError {
+    /// Valid rates are 3 (8192 Hz) to 15 (2 Hz).
+    InvalidRate(u8),
+    /// The HPET legacy replacement route disconnects the RTC from IRQ8.
+    ReplacedByHpet,
+    Irq(IrqError),
+}
+
+/// Takes the century register from the ACPI FADT and logs the current time.
+pub fn init() {
+    if let Some(fadt) = crate::acpi::tables().and_then(|tables| tables.fadt) {
+        RTC.lock().set_century_register(fadt.century_register);
+    }
+    let now = now();
+    ok!(
+        "RTC initialized, it is {} (unix time {})",
+        now,
+        now.unix_timestamp()
+    );
+}
+
+pub fn now() -> DateTime {
+    crate::interrupts::disable_interrupts_for(|| RTC.lock().read_date_time())
+}
+
+/// Raises IRQ8 with 32768 >> (rate - 1) Hz.
+#[allow(dead_code)]
+pub fn enable_periodic_interrupt(rate: u8) -> Result<(), RtcError> {
+    if !(3..=15).contains(&rate) {
+        return Err(RtcError::InvalidRate(rate));
+    }
+    if clock::tick_source() == TickSource::Hpet {
+        return Err(RtcError::ReplacedByHpet);
+    }
+
+    irq::register_irq(irq::RTC_IRQ, periodic_interrupt_handler).map_err(RtcError::Irq)?;
+    crate::interrupts::disable_interrupts_for(|| RTC.lock().enable_periodic_interrupt(rate));
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub fn disable_periodic_interrupt() -> Result<(), RtcError> {
+    crate::interrupts::disable_interrupts_for(|| RTC.lock().disable_periodic_interrupt());
+    irq::unregister_irq(irq::RTC_IRQ, periodic_interrupt_handler).map_err(RtcError::Irq)
+}
+
+/// Number of periodic interrupts since they were enabled.
+#[allow(dead_code)]
+pub fn periodic_interrupts() -> u64 {
+    PERIODIC_INTERRUPTS.load(Ordering::SeqCst)
+}
+
+fn periodic_interrupt_handler(_stack_frame: &InterruptStackFrame) -> IrqReturn {
+    RTC.lock().acknowledge_interrupt();
+    PERIODIC_INTERRUPTS.fetch_add(1, Ordering::SeqCst);
+    IrqReturn::Handled
+}
@@ -0,0 +1,160 @@
+use super::date_time::DateTime;
+use crate::asm::Port;
+
+const SELECT_ADDRESS: u16 = 0x70;
+const DATA_ADDRESS: u16 = 0x71;
+
+const REGISTER_SECOND: u8 = 0x00;
+const REGISTER_MINUTE: u8 = 0x02;
+const REGISTER_HOUR: u8 = 0x04;
+const REGISTER_DAY: u8 = 0x07;
+const REGISTER_MONTH: u8 = 0x08;
+const REGISTER_YEAR: u8 = 0x09;
+const REGISTER_STATUS_A: u8 = 0x0a;
+const REGISTER_STATUS_B: u8 = 0x0b;
+const REGISTER_STATUS_C: u8 = 0x0c;
+
+const STATUS_A_UPDATE_IN_PROGRESS: u8 = 1 << 7;
+const STATUS_A_RATE_MASK: u8 = 0x0f;
+const STATUS_B_24_HOUR: u8 = 1 << 1;
+const STATUS_B_BINARY: u8 = 1 << 2;
+const STATUS_B_PERIODIC_INTERRUPT: u8 = 1 << 6;
+/// Set in the hour register for PM in 12 hour mode.
+const HOUR_PM: u8 = 1 << 7;
+
+/// Used if the ACPI FADT does not name a century register.
+const DEFAULT_CENTURY: u16 = 20;
+
+/// The raw register values of one read.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Registers {
+    second: u8,
+    minute: u8,
+    hour: u8,
+    day: u8,
+    month: u8,
+    year: u8,
+    century: u8,
+}
+
+/// The real-time clock of the CMOS.
+pub struct Rtc {
+    select: Port,
+    data: Port,
+    /// CMOS register of the century, 0 if there is none.
+    century_register: u8,
+}
+
+impl Rtc {
+    pub const fn new() -> Self {
+        Rtc {
+            select: Port::new(SELECT_ADDRESS),
+            data: Port::new(DATA_ADDRESS),
+            century_register: 0,
+        }
+    }
+
+    pub fn set_century_register(&mut self, register: u8) {
+        self.century_register = register;
+    }
+
+    /// The RTC updates its registers once a second. Reading until two reads
+    /// outside of an update agree avoids torn values.
+    pub fn read_date_time(&self) -> DateTime {
+        let mut registers = self.read_registers();
+        loop {
+            let again = self.read_registers();
+            if again == registers {
+                break;
+            }
+            registers = again;
+        }
+
+        let status_b = self.read(REGISTER_STATUS_B);
+        let binary = status_b & STATUS_B_BINARY != 0;
+        let convert = |value: u8| if binary { value } else { bcd_to_binary(value) };
+
+        let pm = registers.hour & HOUR_PM != 0;
+        let mut hour = convert(registers.hour & !HOUR_PM);
+        if status_b & STATUS_B_24_HOUR == 0 {
+            // 12 hour mode counts 12, 1, ..., 11
+            hour %= 12;
+            if pm {
+                hour += 12;
+            }
+        }
+
+        let century = if self.century_register != 0 {
+            convert(registers.century) as u16
+        } else {
+            DEFAULT_CENTURY
+        };
+
+        DateTime {
+            year: century * 100 + convert(registers.year) as u16,
+            month: convert(registers.month),
+            day: convert(registers.day),
+            hour,
+            minute: convert(registers.minute),
+            second: convert(registers.second),
+        }
+    }
+
+    /// Raises IRQ8 with 32768 >> (rate - 1) Hz, for rates from 3 (8192 Hz) to 15 (2 Hz).
+    pub fn enable_periodic_interrupt(&mut self, rate: u8) {
+        let status_a = self.read(REGISTER_STATUS_A) & !STATUS_A_RATE_MASK;
+        self.write(REGISTER_STATUS_A, status_a | (rate & STATUS_A_RATE_MASK));
+        let status_b = self.read(REGISTER_STATUS_B);
+        self.write(REGISTER_STATUS_B, status_b | STATUS_B_PERIODIC_INTERRUPT);
+        self.acknowledge_interrupt();
+    }
+
+    pub fn disable_periodic_interrupt(&mut self) {
+        let status_b = self.read(REGISTER_STATUS_B);
+        self.write(REGISTER_STATUS_B, status_b & !STATUS_B_PERIODIC_INTERRUPT);
+    }
+
+    /// Status register C must be read after every interrupt, otherwise the
+    /// RTC does not raise another one.
+    pub fn acknowledge_interrupt(&self) -> u8 {
+        self.read(REGISTER_STATUS_C)
+    }
+
+    fn read_registers(&self) -> Registers {
+        while self.read(REGISTER_STATUS_A) & STATUS_A_UPDATE_IN_PROGRESS != 0 {
+            core::hint::spin_loop();
+        }
+
+        Registers {
+            second: self.read(REGISTER_SECOND),
+            minute: self.read(REGISTER_MINUTE),
+            hour: self.read(REGISTER_HOUR),
+            day: self.read(REGISTER_DAY),
+            month: self.read(REGISTER_MONTH),
+            year: self.read(REGISTER_YEAR),
+            century: if self.century_register != 0 {
+                self.read(self.century_register)
+            } else {
+                0
+            },
+        }
+    }
+
+    fn read(&self, register: u8) -> u8 {
+        unsafe {
+            self.select.write(register);
+            self.data.read()
+        }
+    }
+
+    fn write(&mut self, register: u8, value: u8) {
+        unsafe {
+            self.select.write(register);
+            self.data.write(value);
+        }
+    }
+}
+
+fn bcd_to_binary(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0f)
+}
@@ -2,10 +2,14 @@ pub mod serial;
 
 pub use serial::*;
 
+use crate::interrupts::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::interrupts::irq::{self, IrqReturn};
+use crate::ylib::sync::ring_buffer::RingBuffer;
 use crate::ylib::sync::{lazy::Lazy, mutex::Mutex};
 use core::fmt;
 
 const COM1_ADDRESS: u16 = 0x3f8;
+const INPUT_BUFFER_SIZE: usize = 256;
 
 static COM1: Lazy<Mutex<SerialPort>, fn() -> Mutex<SerialPort>> = Lazy::new(|| {
     let serial = SerialPort::create_and_init(COM1_ADDRESS);
@@ -13,6 +17,11 @@ static COM1: Lazy<Mutex<SerialPort>, fn() -> Mutex<SerialPort>> = Lazy::new(|| {
     Mutex::new(serial)
 });
 
+/// Only reads the receive registers, so the interrupt handler does not need
+/// the lock of COM1.
+static COM1_RECEIVER: SerialPort = SerialPort::new(COM1_ADDRESS);
+static INPUT: RingBuffer<u8, INPUT_BUFFER_SIZE> = RingBuffer::new();
+
 #[macro_export]
 macro_rules! serial_print {
     ($($arg:tt)*) => ($crate::serial::_serial_print(format_args!($($arg)*)));
@@ -27,5 +36,31 @@ macro_rules! serial_println {
 #[doc(hidden)]
 pub fn _serial_print(args: fmt::Arguments) {
     use core::fmt::Write;
-    COM1.lock().write_fmt(args).unwrap();
+    crate::interrupts::disable_interrupts_for(|| {
+        COM1.lock().write_fmt(args).unwrap();
+    })
+}
+
+/// Collects the bytes received on COM1 with IRQ4.
+pub fn init_input() {
+    irq::register_irq(irq::SERIAL_IRQ, receive_handler).expect("Could not register COM1 handler");
+    crate::interrupts::disable_interrupts_for(|| COM1.lock().enable_receive_interrupt());
+    ok!("COM1 input initialized");
+}
+
+pub fn has_input() -> bool {
+    !INPUT.is_empty()
+}
+
+pub fn read_byte() -> Option<u8> {
+    INPUT.pop()
+}
+
+fn receive_handler(_stack_frame: &InterruptStackFrame) -> IrqReturn {
+    // The FIFO can hold several bytes per interrupt, bytes are lost when the
+    // input buffer is full
+    while let Some(byte) = COM1_RECEIVER.read_byte() {
+        let _ = INPUT.push(byte);
+    }
+    IrqReturn::Handled
 }
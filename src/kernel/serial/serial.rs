@@ -8,10 +8,15 @@ pub struct SerialPort {
 }
 
 impl SerialPort {
-    pub fn create_and_init(port: u16) -> Result<Self, SerialIsFaulty> {
-        let port = SerialPort {
+    /// Does not touch the hardware, see `create_and_init`.
+    pub const fn new(port: u16) -> Self {
+        SerialPort {
             port: Port::new(port),
-        };
+        }
+    }
+
+    pub fn create_and_init(port: u16) -> Result<Self, SerialIsFaulty> {
+        let port = SerialPort::new(port);
         let result = port.init();
         match result {
             Ok(_) => Ok(port),
@@ -43,6 +48,22 @@ impl SerialPort {
         Ok(())
     }
 
+    /// Raises the interrupt whenever a byte was received.
+    pub fn enable_receive_interrupt(&self) {
+        unsafe {
+            self.port.write_offset(1, 0x01);
+        }
+    }
+
+    pub fn read_byte(&self) -> Option<u8> {
+        unsafe {
+            if self.port.read_offset(5) & 0x01 == 0 {
+                return None;
+            }
+            Some(self.port.read())
+        }
+    }
+
     fn is_transmit_empty(&self) -> u8 {
         return unsafe { self.port.read_offset(5) & 0x20 };
     }
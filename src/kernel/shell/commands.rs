@@ -0,0 +1,445 @@
+use core::ptr;
+
+use super::{parse_number, Command, ShellError};
+use crate::asm::descriptor_tables;
+use crate::asm::Port;
+use crate::interrupts::{controller, irq};
+use crate::memory::frame_allocator::FRAME_ALLOCATOR;
+use crate::memory::paging::ACTIVE_PAGE_TABLE;
+use crate::memory::task_state_segment::TSS;
+use crate::memory::virt_addr::VirtAddr;
+use crate::{clock, multiboot, power, rtc};
+
+const PAGE_SIZE: u64 = 4096;
+const MAX_DUMP_LENGTH: u64 = 4096;
+const BYTES_PER_LINE: usize = 16;
+const EXCEPTION_COUNT: u64 = 32;
+
+pub static COMMANDS: &[Command] = &[
+    Command {
+        name: "help",
+        usage: "help",
+        description: "List all commands",
+        run: help,
+    },
+    Command {
+        name: "clear",
+        usage: "clear",
+        description: "Clear the screen",
+        run: clear,
+    },
+    Command {
+        name: "mem",
+        usage: "mem <address> [length]",
+        description: "Dump memory as hex and ASCII",
+        run: mem,
+    },
+    Command {
+        name: "gdt",
+        usage: "gdt",
+        description: "Show the entries of the loaded GDT",
+        run: gdt,
+    },
+    Command {
+        name: "idt",
+        usage: "idt [first vector] [count]",
+        description: "Show the entries of the loaded IDT",
+        run: idt,
+    },
+    Command {
+        name: "tss",
+        usage: "tss",
+        description: "Show the task state segment",
+        run: tss,
+    },
+    Command {
+        name: "irqs",
+        usage: "irqs",
+        description: "Show the interrupt counters per vector",
+        run: irqs,
+    },
+    Command {
+        name: "inb",
+        usage: "inb <port>",
+        description: "Read a byte from an I/O port",
+        run: inb,
+    },
+    Command {
+        name: "inw",
+        usage: "inw <port>",
+        description: "Read a word from an I/O port",
+        run: inw,
+    },
+    Command {
+        name: "inl",
+        usage: "inl <port>",
+        description: "Read a double word from an I/O port",
+        run: inl,
+    },
+    Command {
+        name: "outb",
+        usage: "outb <port> <value>",
+        description: "Write a byte to an I/O port",
+        run: outb,
+    },
+    Command {
+        name: "outw",
+        usage: "outw <port> <value>",
+        description: "Write a word to an I/O port",
+        run: outw,
+    },
+    Command {
+        name: "outl",
+        usage: "outl <port> <value>",
+        description: "Write a double word to an I/O port",
+        run: outl,
+    },
+    Command {
+        name: "uptime",
+        usage: "uptime",
+        description: "Show the time since boot and the date",
+        run: uptime,
+    },
+    Command {
+        name: "memmap",
+        usage: "memmap",
+        description: "Show the memory map, frame and heap usage",
+        run: memmap,
+    },
+    Command {
+        name: "reboot",
+        usage: "reboot",
+        description: "Reset the machine",
+        run: reboot,
+    },
+    Command {
+        name: "shutdown",
+        usage: "shutdown",
+        description: "Turn the machine off with ACPI",
+        run: shutdown,
+    },
+];
+
+fn help(_arguments: &[&str]) -> Result<(), ShellError> {
+    for command in COMMANDS {
+        console_println!("{:<24} {}", command.usage, command.description);
+    }
+    Ok(())
+}
+
+fn clear(_arguments: &[&str]) -> Result<(), ShellError> {
+    // Erase the display and move the cursor to the top left corner
+    console_print!("\x1b[2J\x1b[H");
+    Ok(())
+}
+
+fn mem(arguments: &[&str]) -> Result<(), ShellError> {
+    let (address, length) = match arguments {
+        [address] => (parse_number(address)?, 128),
+        [address, length] => (parse_number(address)?, parse_number(length)?),
+        _ => return Err(ShellError::Usage),
+    };
+    if length > MAX_DUMP_LENGTH {
+        return Err(ShellError::OutOfRange(length));
+    }
+
+    // Check every page before touching it, a page fault would be fatal
+    let end = address
+        .checked_add(length)
+        .ok_or(ShellError::OutOfRange(length))?;
+    let mut page = address & !(PAGE_SIZE - 1);
+    while page < end {
+        let mapped = VirtAddr::try_new(page)
+            .ok()
+            .and_then(|page| ACTIVE_PAGE_TABLE.lock().translate(page));
+        if mapped.is_none() {
+            return Err(ShellError::NotMapped(page));
+        }
+        page += PAGE_SIZE;
+    }
+
+    let mut line_address = address;
+    while line_address < end {
+        let count = ((end - line_address) as usize).min(BYTES_PER_LINE);
+        let mut bytes = [0; BYTES_PER_LINE];
+        for (offset, byte) in bytes.iter_mut().enumerate().take(count) {
+            *byte = unsafe { ptr::read_volatile((line_address + offset as u64) as *const u8) };
+        }
+
+        console_print!("{:#018x}:", line_address);
+        for byte in &bytes[..count] {
+            console_print!(" {:02x}", byte);
+        }
+        for _ in count..BYTES_PER_LINE {
+            console_print!("   ");
+        }
+        console_print!("  ");
+        for &byte in &bytes[..count] {
+            let character = if (0x20..0x7f).contains(&byte) {
+                byte as char
+            } else {
+                '.'
+            };
+            console_print!("{}", character);
+        }
+        console_println!();
+        line_address += count as u64;
+    }
+    Ok(())
+}
+
+fn gdt(_arguments: &[&str]) -> Result<(), ShellError> {
+    let pointer = descriptor_tables::sgdt();
+    let base = { pointer.base }.as_u64();
+    let count = (pointer.limit as u64 + 1) / 8;
+    console_println!("GDT at {:#x} with {} entries", base, count);
+
+    let mut index = 0;
+    while index < count {
+        let entry = unsafe { ptr::read_volatile((base + index * 8) as *const u64) };
+        let access = (entry >> 40) & 0xff;
+        let flags = (entry >> 52) & 0xf;
+        let limit = (entry & 0xffff) | ((entry >> 48) & 0xf) << 16;
+        let mut segment_base = ((entry >> 16) & 0xff_ffff) | ((entry >> 56) & 0xff) << 24;
+        let system = access & 0x10 == 0;
+
+        let kind = if entry == 0 {
+            "null"
+        } else if system {
+            match access & 0xf {
+                0x9 => "tss",
+                0xb => "tss (busy)",
+                _ => "system",
+            }
+        } else if access & 0x08 != 0 {
+            "code"
+        } else {
+            "data"
+        };
+
+        // System descriptors take two entries, the second one holds the upper base
+        let mut size = 1;
+        if entry != 0 && system && index + 1 < count {
+            let high = unsafe { ptr::read_volatile((base + (index + 1) * 8) as *const u64) };
+            segment_base |= (high & 0xffff_ffff) << 32;
+            size = 2;
+        }
+
+        console_println!(
+            "{:2} selector {:#06x} {:<10} base {:#x} limit {:#x} access {:#04x} flags {:#x}",
+            index,
+            index * 8,
+            kind,
+            segment_base,
+            limit,
+            access,
+            flags
+        );
+        index += size;
+    }
+    Ok(())
+}
+
+fn idt(arguments: &[&str]) -> Result<(), ShellError> {
+    let (first, count) = match arguments {
+        [] => (0, EXCEPTION_COUNT),
+        [first] => (parse_number(first)?, 1),
+        [first, count] => (parse_number(first)?, parse_number(count)?),
+        _ => return Err(ShellError::Usage),
+    };
+
+    let pointer = descriptor_tables::sidt();
+    let base = { pointer.base }.as_u64();
+    let entries = (pointer.limit as u64 + 1) / 16;
+    if first >= entries {
+        return Err(ShellError::OutOfRange(first));
+    }
+    console_println!("IDT at {:#x} with {} entries", base, entries);
+
+    for vector in first..(first.saturating_add(count)).min(entries) {
+        let address = base + vector * 16;
+        let low = unsafe { ptr::read_volatile(address as *const u64) };
+        let high = unsafe { ptr::read_volatile((address + 8) as *const u64) };
+
+        let options = (low >> 32) & 0xffff;
+        if options & 0x8000 == 0 {
+            console_println!("{:3} not present", vector);
+            continue;
+        }
+        let handler = (low & 0xffff) | ((low >> 48) & 0xffff) << 16 | (high & 0xffff_ffff) << 32;
+        let gate = match (options >> 8) & 0xf {
+            0xe => "interrupt",
+            0xf => "trap",
+            _ => "other",
+        };
+        console_println!(
+            "{:3} handler {:#018x} selector {:#06x} {:<9} dpl {} ist {}",
+            vector,
+            handler,
+            (low >> 16) & 0xffff,
+            gate,
+            (options >> 13) & 0x3,
+            options & 0x7
+        );
+    }
+    Ok(())
+}
+
+fn tss(_arguments: &[&str]) -> Result<(), ShellError> {
+    console_println!("{:#?}", TSS.get_static_ref());
+    Ok(())
+}
+
+fn irqs(_arguments: &[&str]) -> Result<(), ShellError> {
+    console_println!(
+        "Interrupt controller: {}",
+        if controller::uses_apic() {
+            "APIC"
+        } else {
+            "PIC"
+        }
+    );
+    for vector in irq::FIRST_VECTOR..=u8::MAX {
+        let count = irq::count(vector);
+        let handlers = irq::handler_count(vector);
+        if count == 0 && handlers == 0 {
+            continue;
+        }
+
+        match controller::legacy_irq(vector) {
+            Some(line) => console_print!("vector {:3} irq {:2}", vector, line),
+            None => console_print!("vector {:3}       ", vector),
+        }
+        console_println!("  {:10} interrupts  {} handler(s)", count, handlers);
+    }
+    console_println!("spurious: {}", irq::spurious_count());
+    Ok(())
+}
+
+fn port_argument(argument: &str) -> Result<Port, ShellError> {
+    let port = parse_number(argument)?;
+    if port > u16::MAX as u64 {
+        return Err(ShellError::OutOfRange(port));
+    }
+    Ok(Port::new(port as u16))
+}
+
+fn value_argument(argument: &str, max: u64) -> Result<u64, ShellError> {
+    let value = parse_number(argument)?;
+    if value > max {
+        return Err(ShellError::OutOfRange(value));
+    }
+    Ok(value)
+}
+
+fn inb(arguments: &[&str]) -> Result<(), ShellError> {
+    let port = match arguments {
+        [port] => port_argument(port)?,
+        _ => return Err(ShellError::Usage),
+    };
+    console_println!("{:#04x}", unsafe { port.read() });
+    Ok(())
+}
+
+fn inw(arguments: &[&str]) -> Result<(), ShellError> {
+    let port = match arguments {
+        [port] => port_argument(port)?,
+        _ => return Err(ShellError::Usage),
+    };
+    console_println!("{:#06x}", unsafe { port.read_u16() });
+    Ok(())
+}
+
+fn inl(arguments: &[&str]) -> Result<(), ShellError> {
+    let port = match arguments {
+        [port] => port_argument(port)?,
+        _ => return Err(ShellError::Usage),
+    };
+    console_println!("{:#010x}", unsafe { port.read_u32() });
+    Ok(())
+}
+
+fn outb(arguments: &[&str]) -> Result<(), ShellError> {
+    let (port, value) = match arguments {
+        [port, value] => (port_argument(port)?, value_argument(value, u8::MAX as u64)?),
+        _ => return Err(ShellError::Usage),
+    };
+    unsafe { port.write(value as u8) };
+    Ok(())
+}
+
+fn outw(arguments: &[&str]) -> Result<(), ShellError> {
+    let (port, value) = match arguments {
+        [port, value] => (
+            port_argument(port)?,
+            value_argument(value, u16::MAX as u64)?,
+        ),
+        _ => return Err(ShellError::Usage),
+    };
+    unsafe { port.write_u16(value as u16) };
+    Ok(())
+}
+
+fn outl(arguments: &[&str]) -> Result<(), ShellError> {
+    let (port, value) = match arguments {
+        [port, value] => (
+            port_argument(port)?,
+            value_argument(value, u32::MAX as u64)?,
+        ),
+        _ => return Err(ShellError::Usage),
+    };
+    unsafe { port.write_u32(value as u32) };
+    Ok(())
+}
+
+fn uptime(_arguments: &[&str]) -> Result<(), ShellError> {
+    console_println!(
+        "up {:?} ({} ticks of the {:?} at {} Hz, {:?} clock)",
+        clock::uptime(),
+        clock::ticks(),
+        clock::tick_source(),
+        clock::tick_frequency(),
+        clock::source()
+    );
+    console_println!("{}", rtc::now());
+    Ok(())
+}
+
+fn memmap(_arguments: &[&str]) -> Result<(), ShellError> {
+    let memory_map_tag = multiboot::boot_information().and_then(|info| info.memory_map_tag());
+    if let Some(memory_map_tag) = memory_map_tag {
+        for area in memory_map_tag.memory_areas() {
+            console_println!(
+                "{:#014x} - {:#014x} {:>10} KiB {:?}",
+                area.start_address(),
+                area.end_address(),
+                area.size() / 1024,
+                area.area_type()
+            );
+        }
+    }
+
+    let (free, total) = crate::interrupts::disable_interrupts_for(|| {
+        let allocator = FRAME_ALLOCATOR.lock();
+        (allocator.free_frames(), allocator.total_frames())
+    });
+    console_println!("frames: {} of {} free", free, total);
+
+    let heap = crate::memory::heap::statistics();
+    console_println!(
+        "heap: {} of {} bytes free in {} allocations, largest free block {} bytes",
+        heap.free_bytes,
+        heap.heap_size,
+        heap.allocations,
+        heap.largest_free_block
+    );
+    Ok(())
+}
+
+fn reboot(_arguments: &[&str]) -> Result<(), ShellError> {
+    power::reboot()
+}
+
+fn shutdown(_arguments: &[&str]) -> Result<(), ShellError> {
+    Err(ShellError::Power(power::shutdown()))
+}
@@ -0,0 +1,61 @@
+use alloc::vec::Vec;
+
+use crate::console;
+use crate::power::PowerError;
+
+mod commands;
+
+const PROMPT: &str = "> ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellError {
+    /// Wrong number of arguments, the usage of the command is shown.
+    Usage,
+    InvalidNumber,
+    /// The value does not fit, e.g. a port above 0xffff.
+    OutOfRange(u64),
+    NotMapped(u64),
+    Power(PowerError),
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+    pub run: fn(&[&str]) -> Result<(), ShellError>,
+}
+
+/// Reads commands from the keyboard and COM1 forever.
+pub fn run() -> ! {
+    console_println!("YaOS debug shell, type 'help' for a list of commands");
+    loop {
+        let line = console::read_line(PROMPT);
+        let mut words = line.split_whitespace();
+        let name = match words.next() {
+            Some(name) => name,
+            None => continue,
+        };
+        let arguments: Vec<&str> = words.collect();
+
+        match commands::COMMANDS
+            .iter()
+            .find(|command| command.name == name)
+        {
+            Some(command) => match (command.run)(&arguments) {
+                Ok(()) => {}
+                Err(ShellError::Usage) => console_println!("usage: {}", command.usage),
+                Err(error) => console_println!("{}: {:?}", name, error),
+            },
+            None => console_println!("Unknown command '{}', try 'help'", name),
+        }
+    }
+}
+
+/// Parses a hexadecimal number with `0x` prefix or a decimal number.
+pub fn parse_number(text: &str) -> Result<u64, ShellError> {
+    let result = match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    result.map_err(|_| ShellError::InvalidNumber)
+}
@@ -0,0 +1,119 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use core::time::Duration;
+
+use self::wheel::{Timer, TimerId, TimerWheel};
+use crate::clock;
+use crate::interrupts::interrupt_descriptor_table::interrupt_stack_frame::InterruptStackFrame;
+use crate::interrupts::irq::{self, IrqReturn};
+use crate::ylib::sync::mutex::Mutex;
+
+pub mod wheel;
+
+/// Only used outside of interrupt handlers, the tick handler just sets `PENDING`.
+static WHEEL: Mutex<TimerWheel> = Mutex::new(TimerWheel::new());
+/// The timer whose callback is running and whether it was cancelled meanwhile.
+static RUNNING: Mutex<Option<(TimerId, bool)>> = Mutex::new(None);
+
+static PENDING: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+// Deadlines and periods are converted to ticks when a timer is scheduled.
+// Timers which are already scheduled when `pit::set_frequency` changes the
+// tick rate keep their tick counts and expire early or late.
+
+/// Adds a second handler to IRQ0, behind the one of the tick source.
+pub fn init() {
+    irq::register_irq(irq::TIMER_IRQ, tick_handler).expect("Could not register timer handler");
+    ok!(
+        "Timer wheel initialized with a resolution of {} us",
+        1_000_000 / clock::tick_frequency().max(1)
+    );
+}
+
+/// Runs the callback once after the delay.
+#[allow(dead_code)]
+pub fn schedule_once<F>(delay: Duration, callback: F) -> TimerId
+where
+    F: FnMut() + 'static,
+{
+    schedule(delay, None, Box::new(callback))
+}
+
+/// Runs the callback every `period`, starting after one period.
+#[allow(dead_code)]
+pub fn schedule_periodic<F>(period: Duration, callback: F) -> TimerId
+where
+    F: FnMut() + 'static,
+{
+    let period_ticks = duration_to_ticks(period);
+    schedule(period, Some(period_ticks), Box::new(callback))
+}
+
+/// Returns false if the timer already expired or was cancelled before.
+#[allow(dead_code)]
+pub fn cancel(id: TimerId) -> bool {
+    if let Some((running, cancelled)) = RUNNING.lock().as_mut() {
+        if *running == id {
+            *cancelled = true;
+            return true;
+        }
+    }
+    WHEEL.lock().remove(id).is_some()
+}
+
+/// True if ticks happened which were not processed by `run_expired` yet.
+pub fn has_pending() -> bool {
+    PENDING.load(Ordering::SeqCst)
+}
+
+/// Runs the callbacks of all expired timers. Must not be called from an
+/// interrupt handler, the callbacks run with interrupts enabled.
+pub fn run_expired() {
+    if !PENDING.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut expired = Vec::new();
+    WHEEL.lock().advance(clock::ticks(), &mut expired);
+
+    for mut timer in expired {
+        *RUNNING.lock() = Some((timer.id, false));
+        (timer.callback)();
+        let cancelled = RUNNING
+            .lock()
+            .take()
+            .is_some_and(|(_, cancelled)| cancelled);
+
+        if let (Some(period), false) = (timer.period, cancelled) {
+            timer.expires += period;
+            WHEEL.lock().insert(timer);
+        }
+    }
+}
+
+fn schedule(delay: Duration, period: Option<u64>, callback: Box<dyn FnMut()>) -> TimerId {
+    let id = TimerId(NEXT_ID.fetch_add(1, Ordering::SeqCst));
+    let mut wheel = WHEEL.lock();
+    let expires = wheel.current().max(clock::ticks()) + duration_to_ticks(delay);
+    wheel.insert(Timer {
+        id,
+        expires,
+        period,
+        callback,
+    });
+    id
+}
+
+/// Rounds up, so a timer never runs early. At least one tick.
+fn duration_to_ticks(duration: Duration) -> u64 {
+    let frequency = clock::tick_frequency().max(1) as u128;
+    let ticks = (duration.as_nanos() * frequency).div_ceil(1_000_000_000);
+    (ticks as u64).max(1)
+}
+
+fn tick_handler(_stack_frame: &InterruptStackFrame) -> IrqReturn {
+    PENDING.store(true, Ordering::SeqCst);
+    IrqReturn::Handled
+}
@@ -0,0 +1,84 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Number of slots, a timer expiring at tick t is kept in slot t % SLOTS.
+const SLOTS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(pub(super) u64);
+
+pub struct Timer {
+    pub id: TimerId,
+    /// Tick at which the callback runs.
+    pub expires: u64,
+    /// Ticks between two runs of a periodic timer.
+    pub period: Option<u64>,
+    pub callback: Box<dyn FnMut()>,
+}
+
+/// A hashed timing wheel: inserting and cancelling are cheap and each tick
+/// only looks at one slot.
+pub struct TimerWheel {
+    slots: Vec<Vec<Timer>>,
+    /// The last tick which was processed.
+    current: u64,
+}
+
+impl TimerWheel {
+    pub const fn new() -> Self {
+        TimerWheel {
+            slots: Vec::new(),
+            current: 0,
+        }
+    }
+
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Timers which would expire in the past run on the next processed tick.
+    pub fn insert(&mut self, mut timer: Timer) {
+        if self.slots.is_empty() {
+            self.slots.resize_with(SLOTS, Vec::new);
+        }
+        timer.expires = timer.expires.max(self.current + 1);
+        self.slots[timer.expires as usize % SLOTS].push(timer);
+    }
+
+    pub fn remove(&mut self, id: TimerId) -> Option<Timer> {
+        for slot in self.slots.iter_mut() {
+            if let Some(index) = slot.iter().position(|timer| timer.id == id) {
+                return Some(slot.swap_remove(index));
+            }
+        }
+        None
+    }
+
+    /// Processes all ticks up to `now` and moves the expired timers to `expired`.
+    pub fn advance(&mut self, now: u64, expired: &mut Vec<Timer>) {
+        if now <= self.current {
+            return;
+        }
+        if self.slots.is_empty() {
+            self.current = now;
+            return;
+        }
+
+        // After a full round every slot has been looked at
+        let steps = (now - self.current).min(SLOTS as u64);
+        for step in 1..=steps {
+            let slot = &mut self.slots[((self.current + step) % SLOTS as u64) as usize];
+            let mut index = 0;
+            while index < slot.len() {
+                if slot[index].expires <= now {
+                    expired.push(slot.swap_remove(index));
+                } else {
+                    index += 1;
+                }
+            }
+        }
+        self.current = now;
+
+        expired.sort_unstable_by_key(|timer| timer.expires);
+    }
+}
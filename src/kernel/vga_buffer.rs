@@ -1,196 +0,0 @@
-use crate::ylib::primitives::volatile::Volatile;
-use crate::ylib::sync::lazy::Lazy;
-use crate::ylib::sync::mutex::Mutex;
-use core::fmt;
-
-pub const DEFAULT_FOREGOUND_COLOR: Color = Color::Yellow;
-pub const DEFAULT_BACKGROUND_COLOR: Color = Color::Black;
-
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
-pub enum Color {
-    Black = 0,
-    Blue = 1,
-    Green = 2,
-    Cyan = 3,
-    Red = 4,
-    Magenta = 5,
-    Brown = 6,
-    LightGray = 7,
-    DarkGray = 8,
-    LightBlue = 9,
-    LightGreen = 10,
-    LightCyan = 11,
-    LightRed = 12,
-    Pink = 13,
-    Yellow = 14,
-    White = 15,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(transparent)]
-struct ColorCode(u8);
-
-impl ColorCode {
-    const fn new(foreground: Color, background: Color) -> Self {
-        ColorCode(ColorCode::get_color_code(foreground, background))
-    }
-
-    fn change(&mut self, foreground: Color, background: Color) {
-        self.0 = ColorCode::get_color_code(foreground, background);
-    }
-
-    const fn get_color_code(foreground: Color, background: Color) -> u8 {
-        (background as u8) << 4 | (foreground as u8)
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(C)]
-struct ScreenChar {
-    ascii_character: u8,
-    color_code: ColorCode,
-}
-
-const BUFFER_HEIGHT: usize = 25;
-const BUFFER_WIDTH: usize = 80;
-
-#[repr(transparent)]
-struct Buffer {
-    chars: [[Volatile<ScreenChar>; BUFFER_WIDTH]; BUFFER_HEIGHT],
-}
-
-pub struct Writer {
-    column_position: usize,
-    color_code: ColorCode,
-    buffer: &'static mut Buffer,
-}
-
-impl Writer {
-    fn write_byte(&mut self, byte: u8) {
-        match byte {
-            b'\n' => self.new_line(),
-            byte => {
-                if self.column_position >= BUFFER_WIDTH {
-                    self.new_line();
-                }
-
-                let row = BUFFER_HEIGHT - 1;
-                let col = self.column_position;
-
-                let color_code = self.color_code;
-                self.buffer.chars[row][col].write(ScreenChar {
-                    ascii_character: byte,
-                    color_code,
-                });
-                self.column_position += 1;
-            }
-        }
-    }
-
-    pub fn write_string(&mut self, s: &str) {
-        for byte in s.bytes() {
-            match byte {
-                // printable ASCII byte or newline
-                0x20..=0x7e | b'\n' => self.write_byte(byte),
-                // not part of printable ASCII range
-                _ => self.write_byte(0xfe),
-            }
-        }
-    }
-
-    pub fn clear_screen(&mut self) {
-        let blank = ScreenChar {
-            ascii_character: b' ',
-            color_code: self.color_code,
-        };
-        for row in 0..BUFFER_HEIGHT {
-            for col in 0..BUFFER_WIDTH {
-                self.buffer.chars[row][col].write(blank);
-            }
-        }
-    }
-
-    fn new_line(&mut self) {
-        for row in 1..BUFFER_HEIGHT {
-            for col in 0..BUFFER_WIDTH {
-                let character = self.buffer.chars[row][col].read();
-                self.buffer.chars[row - 1][col].write(character);
-            }
-        }
-        self.clear_row(BUFFER_HEIGHT - 1);
-        self.column_position = 0;
-    }
-
-    fn clear_row(&mut self, row: usize) {
-        let blank = ScreenChar {
-            ascii_character: b' ',
-            color_code: self.color_code,
-        };
-        for col in 0..BUFFER_WIDTH {
-            self.buffer.chars[row][col].write(blank);
-        }
-    }
-}
-
-impl fmt::Write for Writer {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.write_string(s);
-        Ok(())
-    }
-}
-
-pub static WRITER: Lazy<Mutex<Writer>, fn() -> Mutex<Writer>> = Lazy::new(|| {
-    Mutex::new(Writer {
-        column_position: 0,
-        color_code: ColorCode::new(DEFAULT_FOREGOUND_COLOR, DEFAULT_BACKGROUND_COLOR),
-        buffer: unsafe { &mut *(0xb8000 as *mut Buffer) },
-    })
-});
-
-#[macro_export]
-macro_rules! clear_screen {
-    () => {
-        $crate::vga_buffer::WRITER.lock().clear_screen();
-    };
-}
-
-#[macro_export]
-macro_rules! print {
-    ($($arg:tt)*) => ($crate::vga_buffer::_print(format_args!($($arg)*)));
-}
-
-#[macro_export]
-macro_rules! println {
-    () => ($crate::print!("\n"));
-    ($($arg:tt)*) => ($crate::print!("{}\n", format_args!($($arg)*)));
-}
-
-macro_rules! ok {
-    ($($arg:tt)*) => {
-        $crate::vga_buffer::_ok(format_args!($($arg)*));
-    };
-}
-
-#[doc(hidden)]
-pub fn _print(args: fmt::Arguments) {
-    use core::fmt::Write;
-    crate::interrupts::disable_interrupts_for(|| {
-        WRITER.lock().write_fmt(args).unwrap();
-    })
-}
-
-#[doc(hidden)]
-pub fn _ok(args: fmt::Arguments) {
-    use core::fmt::Write;
-    crate::interrupts::disable_interrupts_for(|| {
-        let mut lock = WRITER.lock();
-        lock.write_fmt(args).unwrap();
-        lock.color_code.change(Color::Green, Color::Black);
-        lock.write_string(" OK!");
-        lock.color_code
-            .change(DEFAULT_FOREGOUND_COLOR, DEFAULT_BACKGROUND_COLOR);
-        lock.write_string("\n");
-    })
-}
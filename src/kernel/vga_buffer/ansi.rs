@@ -0,0 +1,129 @@
+const ESCAPE: char = '\u{1b}';
+/// Parameters beyond this number are dropped.
+const MAX_PARAMETERS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Ground,
+    Escape,
+    ControlSequence,
+}
+
+/// A complete `ESC [ parameters final` sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ControlSequence {
+    /// Sequences with a `?` before the parameters, e.g. `ESC [ ? 25 l`.
+    pub private: bool,
+    pub final_byte: char,
+    parameters: [u16; MAX_PARAMETERS],
+    count: usize,
+}
+
+impl ControlSequence {
+    const fn new() -> Self {
+        ControlSequence {
+            private: false,
+            final_byte: '\0',
+            parameters: [0; MAX_PARAMETERS],
+            count: 0,
+        }
+    }
+
+    pub fn parameters(&self) -> &[u16] {
+        &self.parameters[..self.count]
+    }
+
+    /// Missing and zero parameters take the default.
+    pub fn parameter(&self, index: usize, default: u16) -> u16 {
+        match self.parameters().get(index) {
+            Some(&value) if value != 0 => value,
+            _ => default,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Print(char),
+    /// C0 control character like `\n`, `\r`, `\t` or backspace.
+    Control(char),
+    /// `ESC` followed by a single character, e.g. `ESC 7` to save the cursor.
+    Escape(char),
+    ControlSequence(ControlSequence),
+}
+
+/// State machine for the subset of ANSI/VT100 escape sequences which makes
+/// sense on a text mode screen.
+pub struct Parser {
+    state: State,
+    sequence: ControlSequence,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser::new()
+    }
+}
+
+impl Parser {
+    pub const fn new() -> Self {
+        Parser {
+            state: State::Ground,
+            sequence: ControlSequence::new(),
+        }
+    }
+
+    pub fn advance(&mut self, character: char) -> Option<Action> {
+        if character == ESCAPE {
+            self.state = State::Escape;
+            return None;
+        }
+
+        match self.state {
+            State::Ground if character.is_control() => Some(Action::Control(character)),
+            State::Ground => Some(Action::Print(character)),
+            State::Escape => {
+                if character == '[' {
+                    self.sequence = ControlSequence::new();
+                    self.state = State::ControlSequence;
+                    None
+                } else {
+                    self.state = State::Ground;
+                    Some(Action::Escape(character))
+                }
+            }
+            State::ControlSequence => self.advance_control_sequence(character),
+        }
+    }
+
+    fn advance_control_sequence(&mut self, character: char) -> Option<Action> {
+        let sequence = &mut self.sequence;
+        match character {
+            '0'..='9' => {
+                if sequence.count == 0 {
+                    sequence.count = 1;
+                }
+                let digit = character as u16 - '0' as u16;
+                let parameter = &mut sequence.parameters[sequence.count - 1];
+                *parameter = parameter.saturating_mul(10).saturating_add(digit);
+            }
+            ';' => {
+                if sequence.count == 0 {
+                    sequence.count = 1;
+                }
+                if sequence.count < MAX_PARAMETERS {
+                    sequence.count += 1;
+                }
+            }
+            '?' if sequence.count == 0 => sequence.private = true,
+            '\u{40}'..='\u{7e}' => {
+                sequence.final_byte = character;
+                self.state = State::Ground;
+                return Some(Action::ControlSequence(*sequence));
+            }
+            // Intermediate bytes are not supported
+            _ => {}
+        }
+        None
+    }
+}
@@ -0,0 +1,78 @@
+use crate::asm::Port;
+
+const CRTC_INDEX: u16 = 0x3d4;
+const CRTC_DATA: u16 = 0x3d5;
+
+const CURSOR_START: u8 = 0x0a;
+const CURSOR_END: u8 = 0x0b;
+const CURSOR_LOCATION_HIGH: u8 = 0x0e;
+const CURSOR_LOCATION_LOW: u8 = 0x0f;
+
+/// Bit of the cursor start register which hides the cursor.
+const CURSOR_DISABLED: u8 = 1 << 5;
+const SCANLINE_MASK: u8 = 0x1f;
+
+/// Scanlines of the cursor within a character cell (0 to 15 in 80x25 mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorShape {
+    pub start: u8,
+    pub end: u8,
+}
+
+impl CursorShape {
+    pub const UNDERLINE: CursorShape = CursorShape { start: 14, end: 15 };
+    #[allow(dead_code)]
+    pub const BLOCK: CursorShape = CursorShape { start: 0, end: 15 };
+}
+
+/// The blinking text mode cursor of the CRT controller.
+pub struct HardwareCursor {
+    index: Port,
+    data: Port,
+}
+
+impl Default for HardwareCursor {
+    fn default() -> Self {
+        HardwareCursor::new()
+    }
+}
+
+impl HardwareCursor {
+    pub const fn new() -> Self {
+        HardwareCursor {
+            index: Port::new(CRTC_INDEX),
+            data: Port::new(CRTC_DATA),
+        }
+    }
+
+    pub fn show(&self, shape: CursorShape) {
+        let start = self.read(CURSOR_START) & !(CURSOR_DISABLED | SCANLINE_MASK);
+        self.write(CURSOR_START, start | (shape.start & SCANLINE_MASK));
+        let end = self.read(CURSOR_END) & !SCANLINE_MASK;
+        self.write(CURSOR_END, end | (shape.end & SCANLINE_MASK));
+    }
+
+    pub fn hide(&self) {
+        self.write(CURSOR_START, CURSOR_DISABLED);
+    }
+
+    /// Moves the cursor to the cell with the index row * width + column.
+    pub fn set_position(&self, cell: u16) {
+        self.write(CURSOR_LOCATION_LOW, cell as u8);
+        self.write(CURSOR_LOCATION_HIGH, (cell >> 8) as u8);
+    }
+
+    fn read(&self, register: u8) -> u8 {
+        unsafe {
+            self.index.write(register);
+            self.data.read()
+        }
+    }
+
+    fn write(&self, register: u8, value: u8) {
+        unsafe {
+            self.index.write(register);
+            self.data.write(value);
+        }
+    }
+}
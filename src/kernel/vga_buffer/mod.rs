@@ -0,0 +1,593 @@
+use crate::ylib::primitives::volatile::Volatile;
+use crate::ylib::sync::lazy::Lazy;
+use crate::ylib::sync::mutex::Mutex;
+use core::fmt;
+
+use self::ansi::{Action, ControlSequence, Parser};
+use self::cursor::{CursorShape, HardwareCursor};
+use self::scrollback::Scrollback;
+
+pub mod ansi;
+pub mod cursor;
+mod scrollback;
+
+pub const DEFAULT_FOREGOUND_COLOR: Color = Color::Yellow;
+pub const DEFAULT_BACKGROUND_COLOR: Color = Color::Black;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Color {
+    Black = 0,
+    Blue = 1,
+    Green = 2,
+    Cyan = 3,
+    Red = 4,
+    Magenta = 5,
+    Brown = 6,
+    LightGray = 7,
+    DarkGray = 8,
+    LightBlue = 9,
+    LightGreen = 10,
+    LightCyan = 11,
+    LightRed = 12,
+    Pink = 13,
+    Yellow = 14,
+    White = 15,
+}
+
+impl Color {
+    /// The colors of the ANSI color codes 0 to 7, bright adds 8.
+    fn from_ansi(index: u16, bright: bool) -> Color {
+        let color = match index {
+            0 => Color::Black,
+            1 => Color::Red,
+            2 => Color::Green,
+            3 => Color::Brown,
+            4 => Color::Blue,
+            5 => Color::Magenta,
+            6 => Color::Cyan,
+            _ => Color::LightGray,
+        };
+        if bright {
+            color.bright()
+        } else {
+            color
+        }
+    }
+
+    fn bright(self) -> Color {
+        match self {
+            Color::Black => Color::DarkGray,
+            Color::Blue => Color::LightBlue,
+            Color::Green => Color::LightGreen,
+            Color::Cyan => Color::LightCyan,
+            Color::Red => Color::LightRed,
+            Color::Magenta => Color::Pink,
+            Color::Brown => Color::Yellow,
+            Color::LightGray => Color::White,
+            bright => bright,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+struct ColorCode(u8);
+
+impl ColorCode {
+    const fn new(foreground: Color, background: Color) -> Self {
+        ColorCode(ColorCode::get_color_code(foreground, background))
+    }
+
+    fn change(&mut self, foreground: Color, background: Color) {
+        self.0 = ColorCode::get_color_code(foreground, background);
+    }
+
+    const fn get_color_code(foreground: Color, background: Color) -> u8 {
+        (background as u8) << 4 | (foreground as u8)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+struct ScreenChar {
+    ascii_character: u8,
+    color_code: ColorCode,
+}
+
+const BUFFER_HEIGHT: usize = 25;
+pub const BUFFER_WIDTH: usize = 80;
+
+/// Lines scrolled by Shift+PageUp and Shift+PageDown.
+const SCROLL_STEP: usize = BUFFER_HEIGHT / 2;
+
+/// Only used by the writer, while its lock is held.
+static SCROLLBACK: Mutex<Scrollback> = Mutex::new(Scrollback::new());
+
+#[repr(transparent)]
+struct Buffer {
+    chars: [[Volatile<ScreenChar>; BUFFER_WIDTH]; BUFFER_HEIGHT],
+}
+
+pub struct Writer {
+    row_position: usize,
+    column_position: usize,
+    color_code: ColorCode,
+    /// Position, colors and intensity stored by `save_cursor`.
+    saved_cursor: (usize, usize, Color, Color, bool),
+    cursor: HardwareCursor,
+    /// Shape of the visible cursor, `None` if it is hidden.
+    cursor_shape: Option<CursorShape>,
+    /// Number of lines the view is scrolled back, 0 shows the live screen.
+    view_offset: usize,
+    parser: Parser,
+    /// Foreground color as selected, shown brightened while `bold` is set.
+    foreground: Color,
+    background: Color,
+    bold: bool,
+    buffer: &'static mut Buffer,
+}
+
+impl Writer {
+    fn write_byte(&mut self, byte: u8) {
+        match byte {
+            b'\n' => self.new_line(),
+            byte => {
+                if self.column_position >= BUFFER_WIDTH {
+                    self.new_line();
+                }
+
+                let row = self.row_position;
+                let col = self.column_position;
+
+                let color_code = self.color_code;
+                self.buffer.chars[row][col].write(ScreenChar {
+                    ascii_character: byte,
+                    color_code,
+                });
+                self.column_position += 1;
+            }
+        }
+    }
+
+    pub fn write_string(&mut self, s: &str) {
+        self.show_live_screen();
+        for character in s.chars() {
+            self.write_character(character);
+        }
+        self.update_cursor();
+    }
+
+    pub fn write_char(&mut self, character: char) {
+        self.show_live_screen();
+        self.write_character(character);
+        self.update_cursor();
+    }
+
+    /// Characters go through the escape sequence parser first.
+    fn write_character(&mut self, character: char) {
+        if let Some(action) = self.parser.advance(character) {
+            self.execute(action);
+        }
+    }
+
+    fn execute(&mut self, action: Action) {
+        match action {
+            Action::Print(character) => self.write_byte(code_page_437(character)),
+            Action::Control(character) => self.control(character),
+            Action::Escape('7') => self.save_cursor(),
+            Action::Escape('8') => self.restore_cursor(),
+            Action::Escape('c') => {
+                self.reset_color();
+                self.clear_screen();
+            }
+            Action::Escape(_) => {}
+            Action::ControlSequence(sequence) => self.control_sequence(&sequence),
+        }
+    }
+
+    fn control(&mut self, character: char) {
+        match character {
+            '\n' => self.new_line(),
+            '\r' => self.column_position = 0,
+            '\u{8}' => self.column_position = self.column_position.saturating_sub(1),
+            '\t' => self.column_position = ((self.column_position / 8 + 1) * 8).min(BUFFER_WIDTH),
+            _ => {}
+        }
+    }
+
+    fn control_sequence(&mut self, sequence: &ControlSequence) {
+        let count = sequence.parameter(0, 1) as usize;
+        let last_row = BUFFER_HEIGHT - 1;
+        let last_column = BUFFER_WIDTH - 1;
+
+        match (sequence.private, sequence.final_byte) {
+            (false, 'm') => self.select_graphic_rendition(sequence),
+            (false, 'A') => self.row_position = self.row_position.saturating_sub(count),
+            (false, 'B') => self.row_position = (self.row_position + count).min(last_row),
+            (false, 'C') => self.column_position = (self.column_position + count).min(last_column),
+            (false, 'D') => self.column_position = self.column_position.saturating_sub(count),
+            (false, 'G') => self.column_position = (count - 1).min(last_column),
+            (false, 'H') | (false, 'f') => {
+                let row = sequence.parameter(0, 1) as usize - 1;
+                let column = sequence.parameter(1, 1) as usize - 1;
+                self.set_position(row, column.min(last_column));
+            }
+            (false, 'K') => self.erase_in_line(sequence.parameter(0, 0)),
+            (false, 'J') => self.erase_in_display(sequence.parameter(0, 0)),
+            (false, 's') => self.save_cursor(),
+            (false, 'u') => self.restore_cursor(),
+            // Show and hide the cursor
+            (true, 'h') if sequence.parameters() == [25] => {
+                self.show_cursor(CursorShape::UNDERLINE)
+            }
+            (true, 'l') if sequence.parameters() == [25] => self.hide_cursor(),
+            _ => {}
+        }
+    }
+
+    /// Colors and intensity, other attributes cannot be shown in text mode.
+    fn select_graphic_rendition(&mut self, sequence: &ControlSequence) {
+        if sequence.parameters().is_empty() {
+            self.reset_color();
+            return;
+        }
+
+        let (mut foreground, mut background) = (self.foreground, self.background);
+        let mut bold = self.bold;
+        for &parameter in sequence.parameters() {
+            match parameter {
+                0 => {
+                    foreground = DEFAULT_FOREGOUND_COLOR;
+                    background = DEFAULT_BACKGROUND_COLOR;
+                    bold = false;
+                }
+                1 => bold = true,
+                22 => bold = false,
+                30..=37 => foreground = Color::from_ansi(parameter - 30, false),
+                39 => foreground = DEFAULT_FOREGOUND_COLOR,
+                40..=47 => background = Color::from_ansi(parameter - 40, false),
+                49 => background = DEFAULT_BACKGROUND_COLOR,
+                90..=97 => foreground = Color::from_ansi(parameter - 90, true),
+                100..=107 => background = Color::from_ansi(parameter - 100, true),
+                _ => {}
+            }
+        }
+        self.bold = bold;
+        self.set_color(foreground, background);
+    }
+
+    /// 0 erases to the end of the line, 1 to its start and 2 all of it.
+    fn erase_in_line(&mut self, mode: u16) {
+        let columns = match mode {
+            0 => self.column_position..BUFFER_WIDTH,
+            1 => 0..(self.column_position + 1).min(BUFFER_WIDTH),
+            _ => 0..BUFFER_WIDTH,
+        };
+        let blank = self.blank();
+        for col in columns {
+            self.buffer.chars[self.row_position][col].write(blank);
+        }
+    }
+
+    /// 0 erases to the end of the screen, 1 to its start and 2 all of it.
+    /// The cursor does not move.
+    fn erase_in_display(&mut self, mode: u16) {
+        let rows = match mode {
+            0 => self.row_position + 1..BUFFER_HEIGHT,
+            1 => 0..self.row_position,
+            _ => 0..BUFFER_HEIGHT,
+        };
+        for row in rows {
+            self.clear_row(row);
+        }
+        if mode < 2 {
+            self.erase_in_line(mode);
+        }
+    }
+
+    /// Writes a cell directly, without moving the write position.
+    pub fn write_cell(
+        &mut self,
+        row: usize,
+        column: usize,
+        character: char,
+        foreground: Color,
+        background: Color,
+    ) {
+        if row >= BUFFER_HEIGHT || column >= BUFFER_WIDTH {
+            return;
+        }
+        self.show_live_screen();
+        self.buffer.chars[row][column].write(ScreenChar {
+            ascii_character: code_page_437(character),
+            color_code: ColorCode::new(foreground, background),
+        });
+    }
+
+    /// Selects the colors, the foreground stays bright while bold is set.
+    pub fn set_color(&mut self, foreground: Color, background: Color) {
+        self.foreground = foreground;
+        self.background = background;
+        let shown = if self.bold {
+            foreground.bright()
+        } else {
+            foreground
+        };
+        self.color_code.change(shown, background);
+    }
+
+    pub fn reset_color(&mut self) {
+        self.bold = false;
+        self.set_color(DEFAULT_FOREGOUND_COLOR, DEFAULT_BACKGROUND_COLOR);
+    }
+
+    pub fn column(&self) -> usize {
+        self.column_position
+    }
+
+    /// Moves the write position within the current line.
+    pub fn set_column(&mut self, column: usize) {
+        self.column_position = column.min(BUFFER_WIDTH);
+        self.update_cursor();
+    }
+
+    /// Row and column of the write position.
+    pub fn position(&self) -> (usize, usize) {
+        (self.row_position, self.column_position)
+    }
+
+    pub fn set_position(&mut self, row: usize, column: usize) {
+        self.row_position = row.min(BUFFER_HEIGHT - 1);
+        self.column_position = column.min(BUFFER_WIDTH);
+        self.update_cursor();
+    }
+
+    /// Stores the position, the colors and bold for `restore_cursor`.
+    pub fn save_cursor(&mut self) {
+        self.saved_cursor = (
+            self.row_position,
+            self.column_position,
+            self.foreground,
+            self.background,
+            self.bold,
+        );
+    }
+
+    pub fn restore_cursor(&mut self) {
+        let (row, column, foreground, background, bold) = self.saved_cursor;
+        self.bold = bold;
+        self.set_color(foreground, background);
+        self.set_position(row, column);
+    }
+
+    pub fn show_cursor(&mut self, shape: CursorShape) {
+        self.cursor_shape = Some(shape);
+        if self.view_offset == 0 {
+            self.cursor.show(shape);
+            self.update_cursor();
+        }
+    }
+
+    pub fn hide_cursor(&mut self) {
+        self.cursor_shape = None;
+        self.cursor.hide();
+    }
+
+    /// Shows older lines from the scrollback buffer.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.set_view_offset(self.view_offset + lines);
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.set_view_offset(self.view_offset.saturating_sub(lines));
+    }
+
+    /// Output always goes to the live screen, so it is shown again first.
+    fn show_live_screen(&mut self) {
+        if self.view_offset != 0 {
+            self.set_view_offset(0);
+        }
+    }
+
+    /// Draws the scrollback buffer followed by the live screen, scrolled back
+    /// by `offset` lines. The live screen is stored while it is not visible.
+    fn set_view_offset(&mut self, offset: usize) {
+        let mut scrollback = SCROLLBACK.lock();
+        let offset = offset.min(scrollback.len());
+        if offset == self.view_offset {
+            return;
+        }
+
+        if self.view_offset == 0 {
+            for (row, line) in scrollback.screen_mut().iter_mut().enumerate() {
+                for (col, character) in line.iter_mut().enumerate() {
+                    *character = self.buffer.chars[row][col].read();
+                }
+            }
+            self.cursor.hide();
+        }
+        self.view_offset = offset;
+
+        let history = scrollback.len();
+        for row in 0..BUFFER_HEIGHT {
+            let index = history + row - offset;
+            let line = if index < history {
+                scrollback.line(index)
+            } else {
+                &scrollback.screen()[index - history]
+            };
+            for (col, &character) in line.iter().enumerate() {
+                self.buffer.chars[row][col].write(character);
+            }
+        }
+
+        if offset == 0 {
+            if let Some(shape) = self.cursor_shape {
+                self.cursor.show(shape);
+            }
+            self.update_cursor();
+        }
+    }
+
+    /// Blanks the current line from the write position to its end.
+    pub fn clear_to_end_of_line(&mut self) {
+        self.show_live_screen();
+        self.erase_in_line(0);
+    }
+
+    /// Blanks the screen and moves the write position to the top left corner.
+    pub fn clear_screen(&mut self) {
+        self.show_live_screen();
+        for row in 0..BUFFER_HEIGHT {
+            self.clear_row(row);
+        }
+        self.set_position(0, 0);
+    }
+
+    fn new_line(&mut self) {
+        self.column_position = 0;
+        if self.row_position < BUFFER_HEIGHT - 1 {
+            self.row_position += 1;
+            return;
+        }
+
+        let mut top_line = [self.blank(); BUFFER_WIDTH];
+        for (col, character) in top_line.iter_mut().enumerate() {
+            *character = self.buffer.chars[0][col].read();
+        }
+        SCROLLBACK.lock().push(top_line);
+
+        for row in 1..BUFFER_HEIGHT {
+            for col in 0..BUFFER_WIDTH {
+                let character = self.buffer.chars[row][col].read();
+                self.buffer.chars[row - 1][col].write(character);
+            }
+        }
+        self.clear_row(BUFFER_HEIGHT - 1);
+    }
+
+    fn clear_row(&mut self, row: usize) {
+        let blank = self.blank();
+        for col in 0..BUFFER_WIDTH {
+            self.buffer.chars[row][col].write(blank);
+        }
+    }
+
+    fn blank(&self) -> ScreenChar {
+        ScreenChar {
+            ascii_character: b' ',
+            color_code: self.color_code,
+        }
+    }
+
+    /// The hardware cursor shows the write position, at the end of a full
+    /// line it stays on the last column.
+    fn update_cursor(&self) {
+        let column = self.column_position.min(BUFFER_WIDTH - 1);
+        self.cursor
+            .set_position((self.row_position * BUFFER_WIDTH + column) as u16);
+    }
+}
+
+/// The VGA text mode font, printable ASCII and a few latin characters of the
+/// keyboard layouts. Everything else is shown as a square.
+fn code_page_437(character: char) -> u8 {
+    match character {
+        ' '..='~' => character as u8,
+        'ü' => 0x81,
+        'ä' => 0x84,
+        'Ä' => 0x8e,
+        'ö' => 0x94,
+        'Ö' => 0x99,
+        'Ü' => 0x9a,
+        'ß' => 0xe1,
+        'µ' => 0xe6,
+        '°' => 0xf8,
+        '²' => 0xfd,
+        '§' => 0x15,
+        _ => 0xfe,
+    }
+}
+
+impl fmt::Write for Writer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_string(s);
+        Ok(())
+    }
+}
+
+pub static WRITER: Lazy<Mutex<Writer>, fn() -> Mutex<Writer>> = Lazy::new(|| {
+    let color_code = ColorCode::new(DEFAULT_FOREGOUND_COLOR, DEFAULT_BACKGROUND_COLOR);
+    let mut writer = Writer {
+        row_position: BUFFER_HEIGHT - 1,
+        column_position: 0,
+        color_code,
+        saved_cursor: (
+            BUFFER_HEIGHT - 1,
+            0,
+            DEFAULT_FOREGOUND_COLOR,
+            DEFAULT_BACKGROUND_COLOR,
+            false,
+        ),
+        cursor: HardwareCursor::new(),
+        cursor_shape: None,
+        view_offset: 0,
+        parser: Parser::new(),
+        foreground: DEFAULT_FOREGOUND_COLOR,
+        background: DEFAULT_BACKGROUND_COLOR,
+        bold: false,
+        buffer: unsafe { &mut *(0xb8000 as *mut Buffer) },
+    };
+    writer.show_cursor(CursorShape::UNDERLINE);
+    Mutex::new(writer)
+});
+
+#[macro_export]
+macro_rules! clear_screen {
+    () => {
+        $crate::vga_buffer::WRITER.lock().clear_screen();
+    };
+}
+
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => ($crate::vga_buffer::_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! println {
+    () => ($crate::print!("\n"));
+    ($($arg:tt)*) => ($crate::print!("{}\n", format_args!($($arg)*)));
+}
+
+macro_rules! ok {
+    ($($arg:tt)*) => {
+        $crate::vga_buffer::_ok(format_args!($($arg)*));
+    };
+}
+
+/// Scrolls half a screen back into the scrollback buffer.
+pub fn scroll_up() {
+    crate::interrupts::disable_interrupts_for(|| WRITER.lock().scroll_up(SCROLL_STEP));
+}
+
+pub fn scroll_down() {
+    crate::interrupts::disable_interrupts_for(|| WRITER.lock().scroll_down(SCROLL_STEP));
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    crate::interrupts::disable_interrupts_for(|| {
+        WRITER.lock().write_fmt(args).unwrap();
+    })
+}
+
+#[doc(hidden)]
+pub fn _ok(args: fmt::Arguments) {
+    // Green "OK!", the same escape sequences work on the screen and on COM1
+    _print(format_args!("{} \x1b[32mOK!\x1b[0m\n", args));
+    crate::serial::_serial_print(format_args!("{} \x1b[32mOK!\x1b[0m\n", args));
+}
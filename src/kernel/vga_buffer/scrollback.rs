@@ -0,0 +1,58 @@
+use super::{ColorCode, ScreenChar, BUFFER_HEIGHT, BUFFER_WIDTH};
+
+/// Number of lines kept above the screen.
+pub const SCROLLBACK_LINES: usize = 500;
+
+type Line = [ScreenChar; BUFFER_WIDTH];
+
+/// All zero, so the buffer ends up in .bss. Unused lines are never shown.
+const EMPTY: ScreenChar = ScreenChar {
+    ascii_character: 0,
+    color_code: ColorCode(0),
+};
+
+/// Lines which were scrolled off the top of the screen, the oldest ones are
+/// overwritten once it is full. Also keeps the live screen while an older
+/// part is shown.
+pub struct Scrollback {
+    lines: [Line; SCROLLBACK_LINES],
+    /// Index the next line is stored at.
+    next: usize,
+    count: usize,
+    screen: [Line; BUFFER_HEIGHT],
+}
+
+impl Scrollback {
+    pub const fn new() -> Self {
+        Scrollback {
+            lines: [[EMPTY; BUFFER_WIDTH]; SCROLLBACK_LINES],
+            next: 0,
+            count: 0,
+            screen: [[EMPTY; BUFFER_WIDTH]; BUFFER_HEIGHT],
+        }
+    }
+
+    pub fn push(&mut self, line: Line) {
+        self.lines[self.next] = line;
+        self.next = (self.next + 1) % SCROLLBACK_LINES;
+        self.count = (self.count + 1).min(SCROLLBACK_LINES);
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Line `index` of the stored lines, 0 is the oldest one.
+    pub fn line(&self, index: usize) -> &Line {
+        let oldest = (self.next + SCROLLBACK_LINES - self.count) % SCROLLBACK_LINES;
+        &self.lines[(oldest + index) % SCROLLBACK_LINES]
+    }
+
+    pub fn screen(&self) -> &[Line; BUFFER_HEIGHT] {
+        &self.screen
+    }
+
+    pub fn screen_mut(&mut self) -> &mut [Line; BUFFER_HEIGHT] {
+        &mut self.screen
+    }
+}
@@ -0,0 +1,66 @@
+use core::alloc::Layout;
+use core::ptr;
+
+use super::{align_up, HeapAllocator, HeapStatistics};
+
+/// Hands out memory linearly and only reclaims it when all allocations are freed.
+///
+/// Very fast and without overhead, which makes it useful during early boot.
+pub struct BumpAllocator {
+    heap_start: usize,
+    heap_end: usize,
+    next: usize,
+    allocations: usize,
+}
+
+impl BumpAllocator {
+    pub const fn new() -> Self {
+        BumpAllocator {
+            heap_start: 0,
+            heap_end: 0,
+            next: 0,
+            allocations: 0,
+        }
+    }
+}
+
+impl HeapAllocator for BumpAllocator {
+    unsafe fn init(&mut self, heap_start: usize, heap_size: usize) {
+        self.heap_start = heap_start;
+        self.heap_end = heap_start + heap_size;
+        self.next = heap_start;
+    }
+
+    fn allocate(&mut self, layout: Layout) -> *mut u8 {
+        let start = align_up(self.next, layout.align());
+        let end = match start.checked_add(layout.size()) {
+            Some(end) => end,
+            None => return ptr::null_mut(),
+        };
+
+        if end > self.heap_end {
+            return ptr::null_mut();
+        }
+
+        self.next = end;
+        self.allocations += 1;
+        start as *mut u8
+    }
+
+    unsafe fn deallocate(&mut self, _ptr: *mut u8, _layout: Layout) {
+        self.allocations -= 1;
+        if self.allocations == 0 {
+            self.next = self.heap_start;
+        }
+    }
+
+    fn statistics(&self) -> HeapStatistics {
+        HeapStatistics {
+            heap_size: self.heap_end - self.heap_start,
+            allocations: self.allocations,
+            used_bytes: self.next - self.heap_start,
+            free_bytes: self.heap_end - self.next,
+            largest_free_block: self.heap_end - self.next,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use core::alloc::Layout;
+use core::ptr;
+
+use super::linked_list::LinkedListAllocator;
+use super::{HeapAllocator, HeapStatistics};
+
+/// The block sizes to use. Each size is also used as alignment of the blocks,
+/// so they must be powers of two.
+const BLOCK_SIZES: &[usize] = &[8, 16, 32, 64, 128, 256, 512, 1024, 2048];
+
+struct BlockNode {
+    next: *mut BlockNode,
+}
+
+/// Serves small allocations from free lists of fixed size blocks and falls back
+/// to a linked list allocator for bigger ones and to create new blocks.
+pub struct FixedSizeBlockAllocator {
+    list_heads: [*mut BlockNode; BLOCK_SIZES.len()],
+    fallback_allocator: LinkedListAllocator,
+    allocations: usize,
+}
+
+impl FixedSizeBlockAllocator {
+    pub const fn new() -> Self {
+        FixedSizeBlockAllocator {
+            list_heads: [ptr::null_mut(); BLOCK_SIZES.len()],
+            fallback_allocator: LinkedListAllocator::new(),
+            allocations: 0,
+        }
+    }
+
+    /// Index of the smallest block size which fits the layout.
+    fn list_index(layout: &Layout) -> Option<usize> {
+        let required_block_size = layout.size().max(layout.align());
+        BLOCK_SIZES
+            .iter()
+            .position(|&size| size >= required_block_size)
+    }
+}
+
+impl HeapAllocator for FixedSizeBlockAllocator {
+    unsafe fn init(&mut self, heap_start: usize, heap_size: usize) {
+        self.fallback_allocator.init(heap_start, heap_size);
+    }
+
+    fn allocate(&mut self, layout: Layout) -> *mut u8 {
+        let ptr = match Self::list_index(&layout) {
+            Some(index) => {
+                let head = self.list_heads[index];
+                if head.is_null() {
+                    // No block left, create a new one
+                    let block_size = BLOCK_SIZES[index];
+                    let layout = Layout::from_size_align(block_size, block_size).unwrap();
+                    self.fallback_allocator.allocate(layout)
+                } else {
+                    self.list_heads[index] = unsafe { (*head).next };
+                    head as *mut u8
+                }
+            }
+            None => self.fallback_allocator.allocate(layout),
+        };
+
+        if !ptr.is_null() {
+            self.allocations += 1;
+        }
+        ptr
+    }
+
+    unsafe fn deallocate(&mut self, ptr: *mut u8, layout: Layout) {
+        match Self::list_index(&layout) {
+            Some(index) => {
+                // Blocks are never given back to the fallback allocator
+                let node = ptr as *mut BlockNode;
+                node.write(BlockNode {
+                    next: self.list_heads[index],
+                });
+                self.list_heads[index] = node;
+            }
+            None => self.fallback_allocator.deallocate(ptr, layout),
+        }
+        self.allocations -= 1;
+    }
+
+    fn statistics(&self) -> HeapStatistics {
+        let mut statistics = self.fallback_allocator.statistics();
+
+        for (index, &head) in self.list_heads.iter().enumerate() {
+            let mut current = head;
+            while !current.is_null() {
+                statistics.free_bytes += BLOCK_SIZES[index];
+                statistics.used_bytes -= BLOCK_SIZES[index];
+                statistics.largest_free_block =
+                    statistics.largest_free_block.max(BLOCK_SIZES[index]);
+                current = unsafe { (*current).next };
+            }
+        }
+
+        statistics.allocations = self.allocations;
+        statistics
+    }
+}
@@ -0,0 +1,175 @@
+use core::alloc::Layout;
+use core::mem;
+use core::ptr;
+
+use super::{align_up, HeapAllocator, HeapStatistics};
+
+/// Header which is stored at the start of every free region.
+struct ListNode {
+    size: usize,
+    next: *mut ListNode,
+}
+
+impl ListNode {
+    fn start_address(&self) -> usize {
+        self as *const ListNode as usize
+    }
+
+    fn end_address(&self) -> usize {
+        self.start_address() + self.size
+    }
+}
+
+/// First-fit allocator which keeps the free regions in a list sorted by
+/// address, so neighbouring regions can be merged on deallocation.
+pub struct LinkedListAllocator {
+    /// Dummy node, `head.next` is the free region with the lowest address.
+    head: ListNode,
+    heap_size: usize,
+    allocations: usize,
+    used_bytes: usize,
+}
+
+impl LinkedListAllocator {
+    pub const fn new() -> Self {
+        LinkedListAllocator {
+            head: ListNode {
+                size: 0,
+                next: ptr::null_mut(),
+            },
+            heap_size: 0,
+            allocations: 0,
+            used_bytes: 0,
+        }
+    }
+
+    /// Inserts the region into the sorted free list and merges it with
+    /// adjacent free regions.
+    unsafe fn add_free_region(&mut self, address: usize, size: usize) {
+        assert_eq!(align_up(address, mem::align_of::<ListNode>()), address);
+        assert!(size >= mem::size_of::<ListNode>());
+
+        let head: *mut ListNode = &mut self.head;
+        let mut previous = head;
+        while !(*previous).next.is_null() && ((*previous).next as usize) < address {
+            previous = (*previous).next;
+        }
+
+        let next = (*previous).next;
+        let node = address as *mut ListNode;
+        node.write(ListNode { size, next });
+        (*previous).next = node;
+
+        if !next.is_null() && (*node).end_address() == next as usize {
+            (*node).size += (*next).size;
+            (*node).next = (*next).next;
+        }
+
+        if previous != head && (*previous).end_address() == address {
+            (*previous).size += (*node).size;
+            (*previous).next = (*node).next;
+        }
+    }
+
+    /// Returns the start and end address of the allocation if it fits into the region.
+    fn fits(region: &ListNode, size: usize, align: usize) -> Option<(usize, usize)> {
+        let mut start = align_up(region.start_address(), align);
+
+        // The padding in front of the allocation must be able to hold a ListNode
+        if start != region.start_address()
+            && start - region.start_address() < mem::size_of::<ListNode>()
+        {
+            start = align_up(region.start_address() + mem::size_of::<ListNode>(), align);
+        }
+
+        let end = start.checked_add(size)?;
+        if end > region.end_address() {
+            return None;
+        }
+
+        // The rest of the region must be able to hold a ListNode as well
+        let excess_size = region.end_address() - end;
+        if excess_size > 0 && excess_size < mem::size_of::<ListNode>() {
+            return None;
+        }
+
+        Some((start, end))
+    }
+
+    /// Adjusts the layout so that the freed memory can always hold a ListNode.
+    fn size_align(layout: Layout) -> (usize, usize) {
+        let layout = layout
+            .align_to(mem::align_of::<ListNode>())
+            .expect("adjusting alignment failed")
+            .pad_to_align();
+        let size = layout.size().max(mem::size_of::<ListNode>());
+        (size, layout.align())
+    }
+}
+
+impl HeapAllocator for LinkedListAllocator {
+    unsafe fn init(&mut self, heap_start: usize, heap_size: usize) {
+        self.heap_size = heap_size;
+        self.add_free_region(heap_start, heap_size);
+    }
+
+    fn allocate(&mut self, layout: Layout) -> *mut u8 {
+        let (size, align) = Self::size_align(layout);
+
+        unsafe {
+            let mut previous: *mut ListNode = &mut self.head;
+            while !(*previous).next.is_null() {
+                let region = (*previous).next;
+
+                if let Some((start, end)) = Self::fits(&*region, size, align) {
+                    let region_start = (*region).start_address();
+                    let region_end = (*region).end_address();
+                    (*previous).next = (*region).next;
+
+                    if start > region_start {
+                        self.add_free_region(region_start, start - region_start);
+                    }
+                    if region_end > end {
+                        self.add_free_region(end, region_end - end);
+                    }
+
+                    self.allocations += 1;
+                    self.used_bytes += size;
+                    return start as *mut u8;
+                }
+
+                previous = region;
+            }
+        }
+
+        ptr::null_mut()
+    }
+
+    unsafe fn deallocate(&mut self, ptr: *mut u8, layout: Layout) {
+        let (size, _) = Self::size_align(layout);
+        self.add_free_region(ptr as usize, size);
+        self.allocations -= 1;
+        self.used_bytes -= size;
+    }
+
+    fn statistics(&self) -> HeapStatistics {
+        let mut free_bytes = 0;
+        let mut largest_free_block = 0;
+
+        let mut current = self.head.next;
+        while !current.is_null() {
+            let size = unsafe { (*current).size };
+            free_bytes += size;
+            largest_free_block = largest_free_block.max(size);
+            current = unsafe { (*current).next };
+        }
+
+        HeapStatistics {
+            heap_size: self.heap_size,
+            allocations: self.allocations,
+            used_bytes: self.heap_size - free_bytes,
+            free_bytes,
+            largest_free_block,
+        }
+    }
+}
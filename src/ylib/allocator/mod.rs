@@ -0,0 +1,45 @@
+// Only the strategy selected by the cargo features is used by the kernel
+#[allow(dead_code)]
+pub mod bump;
+#[allow(dead_code)]
+pub mod fixed_size_block;
+#[allow(dead_code)]
+pub mod linked_list;
+
+use core::alloc::Layout;
+
+/// Common interface of the heap allocation strategies.
+///
+/// Implementations are not synchronized, they have to be wrapped in a lock
+/// to be used as global allocator.
+pub trait HeapAllocator {
+    /// The memory in `heap_start..heap_start + heap_size` must be mapped and unused.
+    /// This function must only be called once.
+    unsafe fn init(&mut self, heap_start: usize, heap_size: usize);
+
+    /// Returns a null pointer if the allocation cannot be satisfied.
+    fn allocate(&mut self, layout: Layout) -> *mut u8;
+
+    /// `ptr` must have been returned by `allocate` with the same layout.
+    unsafe fn deallocate(&mut self, ptr: *mut u8, layout: Layout);
+
+    fn statistics(&self) -> HeapStatistics;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeapStatistics {
+    pub heap_size: usize,
+    /// Number of live allocations.
+    pub allocations: usize,
+    /// Bytes which cannot be handed out, including allocator overhead.
+    pub used_bytes: usize,
+    pub free_bytes: usize,
+    /// The biggest allocation which could currently succeed.
+    /// Compared to `free_bytes` this shows how fragmented the heap is.
+    pub largest_free_block: usize,
+}
+
+/// Aligns the address upwards to the given alignment (must be a power of two).
+pub fn align_up(address: usize, align: usize) -> usize {
+    (address + align - 1) & !(align - 1)
+}
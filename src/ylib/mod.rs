@@ -1,3 +1,4 @@
+pub mod allocator;
 pub mod primitives;
 pub mod sync;
 pub mod utilities;
@@ -1,3 +1,4 @@
 pub mod lazy;
 pub mod mutex;
+pub mod ring_buffer;
 pub mod spin_lock;
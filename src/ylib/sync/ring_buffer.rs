@@ -0,0 +1,62 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A fixed size queue for one producer and one consumer, e.g. an interrupt
+/// handler and the kernel main loop. Neither side ever blocks.
+pub struct RingBuffer<T: Copy, const N: usize> {
+    buffer: UnsafeCell<[Option<T>; N]>,
+    /// Next slot to read, only written by the consumer.
+    head: AtomicUsize,
+    /// Next slot to write, only written by the producer.
+    tail: AtomicUsize,
+}
+
+// The producer and the consumer never access the same slot at the same time
+unsafe impl<T: Copy, const N: usize> Sync for RingBuffer<T, N> {}
+unsafe impl<T: Copy, const N: usize> Send for RingBuffer<T, N> {}
+
+impl<T: Copy, const N: usize> RingBuffer<T, N> {
+    pub const fn new() -> Self {
+        RingBuffer {
+            buffer: UnsafeCell::new([None; N]),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the value back if the buffer is full.
+    pub fn push(&self, value: T) -> Result<(), T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let next = (tail + 1) % N;
+        if next == self.head.load(Ordering::Acquire) {
+            return Err(value);
+        }
+
+        unsafe {
+            (*self.buffer.get())[tail] = Some(value);
+        }
+        self.tail.store(next, Ordering::Release);
+        Ok(())
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let value = unsafe { (*self.buffer.get())[head].take() };
+        self.head.store((head + 1) % N, Ordering::Release);
+        value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire) == self.tail.load(Ordering::Acquire)
+    }
+
+    /// One slot always stays free to tell a full from an empty buffer.
+    #[allow(dead_code)]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+}